@@ -0,0 +1,116 @@
+use std::fmt;
+use std::io;
+
+/// Adapter that implements [`fmt::Write`] for an [`io::Write`].
+///
+/// Strings are written with [`io::Write::write_all`]. I/O errors are converted to
+/// [`fmt::Error`], which carries no information about the error.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{Report, WriteFmt};
+/// use std::fmt::Write;
+///
+/// let error = std::io::Error::other("could not load config");
+/// let mut writer = WriteFmt::new(Vec::new());
+/// write!(writer, "{}", Report::new(error)).unwrap();
+/// assert_eq!(writer.into_inner(), b"could not load config");
+/// ```
+#[derive(Debug)]
+pub struct WriteFmt<W> {
+    inner: W,
+}
+
+impl<W> WriteFmt<W>
+where
+    W: io::Write,
+{
+    /// Create a new adapter that writes to `inner`.
+    pub fn new(inner: W) -> Self {
+        WriteFmt { inner }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> fmt::Write for WriteFmt<W>
+where
+    W: io::Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+/// Adapter that implements [`io::Write`] for a [`fmt::Write`].
+///
+/// The bytes written must be valid UTF-8. A character may be split across multiple writes.
+/// Writing invalid UTF-8 fails with [`io::ErrorKind::InvalidData`].
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::WriteIo;
+/// use std::io::Write;
+///
+/// let mut writer = WriteIo::new(String::new());
+/// writer.write_all("ä".as_bytes()).unwrap();
+/// assert_eq!(writer.into_inner(), "ä");
+/// ```
+#[derive(Debug)]
+pub struct WriteIo<W> {
+    inner: W,
+    /// The bytes of a character whose encoding is incomplete.
+    pending: Vec<u8>,
+}
+
+impl<W> WriteIo<W>
+where
+    W: fmt::Write,
+{
+    /// Create a new adapter that writes to `inner`.
+    pub fn new(inner: W) -> Self {
+        WriteIo {
+            inner,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> io::Write for WriteIo<W>
+where
+    W: fmt::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let (valid, rest) = match std::str::from_utf8(&self.pending) {
+            Ok(s) => (s, 0),
+            Err(e) if e.error_len().is_none() => {
+                let valid = e.valid_up_to();
+                let s = std::str::from_utf8(&self.pending[..valid]).unwrap();
+                (s, self.pending.len() - valid)
+            }
+            Err(e) => {
+                self.pending.clear();
+                return Err(io::Error::new(io::ErrorKind::InvalidData, e));
+            }
+        };
+        let res = self.inner.write_str(valid);
+        self.pending.drain(..self.pending.len() - rest);
+        res.map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
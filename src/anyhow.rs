@@ -0,0 +1,26 @@
+use crate::{ChainedError, Report};
+
+impl Report<ChainedError> {
+    /// Create a report from an [`anyhow::Error`].
+    ///
+    /// The messages are collected with [`anyhow::Error::chain`], which includes all context that
+    /// was attached to the error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use anyhow::Context;
+    /// use error_reporter::Report;
+    ///
+    /// let result: anyhow::Result<()> = Err(anyhow::anyhow!("permission denied"));
+    /// let error = result.context("could not load config").unwrap_err();
+    /// let report = Report::from_anyhow(error);
+    /// assert_eq!(report.to_string(), "could not load config: permission denied");
+    /// ```
+    pub fn from_anyhow(error: anyhow::Error) -> Self {
+        let mut messages = error.chain().map(|e| e.to_string());
+        let message = messages.next().unwrap_or_default();
+        let causes: Vec<_> = messages.collect();
+        Report::new(ChainedError::new(message, causes))
+    }
+}
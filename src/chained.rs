@@ -0,0 +1,144 @@
+use crate::Report;
+use std::error::Error;
+use std::fmt;
+
+/// An error that consists of a message and an optional source.
+///
+/// This type is used to reconstruct an error chain from its messages, for example after
+/// deserializing an [`ErrorDescription`](crate::ErrorDescription).
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{ChainedError, Report};
+///
+/// let error = ChainedError::new("could not load config", ["permission denied"]);
+/// let report = Report::new(error);
+/// assert_eq!(report.to_string(), "could not load config: permission denied");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainedError {
+    message: String,
+    source: Option<Box<ChainedError>>,
+}
+
+impl ChainedError {
+    /// Create a new error chain from the message of the outermost error and the messages of
+    /// its sources.
+    pub fn new<M, I>(message: M, causes: I) -> Self
+    where
+        M: Into<String>,
+        I: IntoIterator,
+        I::Item: Into<String>,
+        I::IntoIter: DoubleEndedIterator,
+    {
+        let mut source = None;
+        for message in causes.into_iter().rev() {
+            source = Some(Box::new(ChainedError {
+                message: message.into(),
+                source,
+            }));
+        }
+        ChainedError {
+            message: message.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for ChainedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ChainedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as _)
+    }
+}
+
+impl Report<ChainedError> {
+    /// Parses a report that was formatted as a single line with the given
+    /// [separator](Report::separator).
+    ///
+    /// The messages are split at `separator`. This only reconstructs the original chain if none
+    /// of the messages contain `separator`. The returned report uses `separator`, so that it is
+    /// formatted as `s` again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::{ChainedError, Report};
+    ///
+    /// let s = "failed to load config because permission was denied";
+    /// let report = Report::<ChainedError>::parse_with_separator(s, " because ").unwrap();
+    /// assert_eq!(report.describe().causes, ["permission was denied"]);
+    /// assert_eq!(report.to_string(), s);
+    /// ```
+    pub fn parse_with_separator(s: &str, separator: &str) -> Result<Self, TryFromStrError> {
+        if s.is_empty() {
+            return Err(TryFromStrError::Empty);
+        }
+        if separator.is_empty() {
+            return Err(TryFromStrError::EmptySeparator);
+        }
+        let mut messages = s.split(separator);
+        let message = messages.next().unwrap_or_default();
+        let causes: Vec<_> = messages.collect();
+        if message.is_empty() || causes.iter().any(|m| m.is_empty()) {
+            return Err(TryFromStrError::Malformed);
+        }
+        let report = Report::new(ChainedError::new(message, causes));
+        Ok(report.separator(separator.to_string()))
+    }
+}
+
+/// Parses a report that was formatted as a single line with the default separator.
+///
+/// The messages are split at `": "`, regardless of the separator that was used to format the
+/// report. Use [`Report::parse_with_separator`] for reports with a different separator. This
+/// only reconstructs the original chain if none of the messages contain `": "`.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{ChainedError, Report};
+///
+/// let report = Report::<ChainedError>::try_from("could not load config: permission denied");
+/// assert_eq!(
+///     report.unwrap().pretty(true).to_string(),
+///     "could not load config\n\nCaused by:\n      permission denied",
+/// );
+/// ```
+impl TryFrom<&str> for Report<ChainedError> {
+    type Error = TryFromStrError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Report::parse_with_separator(s, ": ")
+    }
+}
+
+/// The error returned when parsing a report fails.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TryFromStrError {
+    /// The string is empty.
+    Empty,
+    /// The string contains an empty message.
+    Malformed,
+    /// The separator is empty.
+    EmptySeparator,
+}
+
+impl fmt::Display for TryFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryFromStrError::Empty => f.write_str("the string is empty"),
+            TryFromStrError::Malformed => f.write_str("the string contains an empty message"),
+            TryFromStrError::EmptySeparator => f.write_str("the separator is empty"),
+        }
+    }
+}
+
+impl Error for TryFromStrError {}
@@ -0,0 +1,40 @@
+use crate::Report;
+use std::io::IsTerminal;
+
+impl<E, F> Report<E, F> {
+    /// Enable [colors](Report::colored) if stderr is a terminal that supports them.
+    ///
+    /// The conventions of [NO_COLOR](https://no-color.org) and
+    /// [CLICOLOR](https://bixense.com/clicolors/) are honored:
+    ///
+    /// - If `CLICOLOR_FORCE` is set and not `0`, colors are enabled.
+    /// - Otherwise, if `NO_COLOR` is set and not empty, or if `CLICOLOR` is `0`, colors are
+    ///   disabled.
+    /// - Otherwise, colors are enabled if stderr is a terminal and `TERM` is not `dumb`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// eprintln!("{}", Report::new(error).pretty(true).color_auto());
+    /// ```
+    pub fn color_auto(self) -> Self {
+        let colored = should_color(std::io::stderr().is_terminal(), |name| {
+            std::env::var(name).ok()
+        });
+        self.colored(colored)
+    }
+}
+
+/// Returns whether colors should be used given the environment accessed via `var`.
+pub(crate) fn should_color(is_terminal: bool, var: impl Fn(&str) -> Option<String>) -> bool {
+    if var("CLICOLOR_FORCE").is_some_and(|v| v != "0") {
+        return true;
+    }
+    if var("NO_COLOR").is_some_and(|v| !v.is_empty()) || var("CLICOLOR").as_deref() == Some("0") {
+        return false;
+    }
+    is_terminal && var("TERM").as_deref() != Some("dumb")
+}
@@ -0,0 +1,77 @@
+use crate::{AsSingleLine, Report, ReportFormat};
+use std::error::Error;
+use std::fmt::{self, Write};
+
+const ELLIPSIS: char = '…';
+
+impl<E, F> Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    /// Format the report as a single line into a stack-allocated string.
+    ///
+    /// The report is always formatted as a single line, regardless of whether pretty-printing is
+    /// enabled. This does not allocate unless the [`Display`](fmt::Display) implementations of
+    /// the errors allocate.
+    ///
+    /// If the output does not fit into `N` bytes, it is truncated at a character boundary and
+    /// `…` is appended if there is room. The truncated string is returned in the `Err` variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    ///
+    /// let s = report.to_compact_string::<64>().unwrap();
+    /// assert_eq!(s, "could not load config: permission denied");
+    ///
+    /// let s = report.to_compact_string::<16>().unwrap_err();
+    /// assert_eq!(s, "could not loa…");
+    /// ```
+    pub fn to_compact_string<const N: usize>(
+        &self,
+    ) -> Result<heapless::String<N>, heapless::String<N>> {
+        let mut writer = Truncating {
+            out: heapless::String::new(),
+            truncated: false,
+        };
+        let _ = write!(writer, "{}", AsSingleLine(self.borrowed()));
+        let mut out = writer.out;
+        if !writer.truncated {
+            return Ok(out);
+        }
+        if N >= ELLIPSIS.len_utf8() {
+            while out.len() + ELLIPSIS.len_utf8() > N {
+                out.pop();
+            }
+            let _ = out.push(ELLIPSIS);
+        }
+        Err(out)
+    }
+}
+
+/// Writer that stops writing once its buffer is full.
+struct Truncating<const N: usize> {
+    out: heapless::String<N>,
+    truncated: bool,
+}
+
+impl<const N: usize> Write for Truncating<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.out.push_str(s).is_ok() {
+            return Ok(());
+        }
+        for c in s.chars() {
+            if self.out.push(c).is_err() {
+                break;
+            }
+        }
+        self.truncated = true;
+        Err(fmt::Error)
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{ChainedError, Report};
+use std::fmt;
+
+/// A structured description of a [`Report`].
+///
+/// This type contains the messages of an error and its causes and whether the report is
+/// pretty-printed. It can be inspected and modified before rendering.
+///
+/// Only the [`pretty`](Self::pretty) option of the report is preserved. All other formatting
+/// options, such as the separator, the numbering of the causes, or the cause prefix, are not
+/// part of the description. The description is formatted with the options of a report created
+/// with [`Report::new`].
+///
+/// With the `serde` feature, this type implements `Serialize` and `Deserialize`. A deserialized
+/// description can be turned back into a report with [`ErrorDescription::into_report`].
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::Report;
+///
+/// let error = std::io::Error::other("could not load config");
+/// let cause = std::io::Error::other("permission denied");
+/// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+///
+/// let mut description = report.describe();
+/// description.causes[0] = "access denied".to_string();
+/// assert_eq!(description.to_string(), "could not load config: access denied");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorDescription {
+    /// The message of the error.
+    pub root_message: String,
+    /// The messages of the causes of the error, starting with the outermost cause.
+    pub causes: Vec<String>,
+    /// Whether the report should be pretty-printed.
+    ///
+    /// This is the only formatting option that is preserved.
+    pub pretty: bool,
+}
+
+impl ErrorDescription {
+    /// Format the description across multiple lines.
+    ///
+    /// This produces the same output as a pretty-printed [`Report`].
+    pub fn to_string_pretty(&self) -> String {
+        self.report().pretty(true).to_string()
+    }
+
+    /// Reconstruct a report from the description.
+    ///
+    /// The report is pretty-printed if [`ErrorDescription::pretty`] is set.
+    pub fn into_report(self) -> Report<ChainedError> {
+        Report::new(ChainedError::new(self.root_message, self.causes)).pretty(self.pretty)
+    }
+
+    fn report(&self) -> Report<ChainedError> {
+        Report::new(ChainedError::new(&*self.root_message, &self.causes))
+    }
+}
+
+/// Formats the description as a single line.
+impl fmt::Display for ErrorDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.report(), f)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::Report;
+use std::borrow::Cow;
+
+/// An error that is identified by a diagnostic code such as `E0308`.
+///
+/// The code is printed in the header of [rustc-style](Report::rustc_style) reports created with
+/// [`Report::diagnostic_code`].
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{DiagnosticCode, Report};
+/// use std::borrow::Cow;
+///
+/// #[derive(Debug)]
+/// struct MismatchedTypes;
+///
+/// impl std::fmt::Display for MismatchedTypes {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("mismatched types")
+///     }
+/// }
+///
+/// impl std::error::Error for MismatchedTypes {}
+///
+/// impl DiagnosticCode for MismatchedTypes {
+///     fn code(&self) -> Option<Cow<'_, str>> {
+///         Some("E0308".into())
+///     }
+/// }
+///
+/// let cause = std::io::Error::other("expected `u32`, found `&str`");
+/// let report = Report::new(MismatchedTypes)
+///     .with_causes(vec![Box::new(cause)])
+///     .diagnostic_code();
+/// assert_eq!(
+///     report.to_string(),
+///     "error[E0308]: mismatched types\n  = note: expected `u32`, found `&str`",
+/// );
+/// ```
+pub trait DiagnosticCode {
+    /// Returns the diagnostic code of this error, if any.
+    fn code(&self) -> Option<Cow<'_, str>>;
+}
+
+impl<E, F> Report<E, F>
+where
+    E: DiagnosticCode,
+{
+    /// Enable rustc-style output with the diagnostic code of the error.
+    ///
+    /// This is a shorthand for calling [`Report::rustc_style`] and [`Report::error_code`] with
+    /// the code returned by [`DiagnosticCode::code`].
+    pub fn diagnostic_code(mut self) -> Self {
+        self.options.error_code = self.error.code().map(|code| code.into_owned().into());
+        self.rustc_style(true)
+    }
+}
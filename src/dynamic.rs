@@ -0,0 +1,87 @@
+use crate::Report;
+use std::error::Error;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// A report to which causes can be added after construction.
+///
+/// The sources of the error are printed first, followed by the causes added via [`Extend`].
+/// The report is formatted like the [`Report`] it wraps, so all formatting options are
+/// available by converting a configured report with `From`. Use [`DynamicReport::into_report`]
+/// to freeze the chain.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{DynamicReport, Report};
+/// use std::error::Error;
+///
+/// let error = std::io::Error::other("could not load plugins");
+/// let mut report = DynamicReport::from(Report::new(error).separator(" | "));
+/// for name in ["a", "b"] {
+///     let error: Box<dyn Error + Send + Sync> = format!("could not load plugin {name}").into();
+///     report.extend([error]);
+/// }
+/// assert_eq!(
+///     report.to_string(),
+///     "could not load plugins | could not load plugin a | could not load plugin b",
+/// );
+/// ```
+pub struct DynamicReport<E> {
+    /// The report that the causes are added to.
+    report: Report<E>,
+}
+
+impl<E> DynamicReport<E>
+where
+    E: Error,
+{
+    /// Create a new `DynamicReport` from an input error.
+    pub fn new(error: E) -> Self {
+        DynamicReport {
+            report: Report::new(error),
+        }
+    }
+
+    /// Enable pretty-printing the report across multiple lines.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.report = self.report.pretty(pretty);
+        self
+    }
+
+    /// Freeze the chain into a [`Report`].
+    pub fn into_report(self) -> Report<E> {
+        self.report
+    }
+}
+
+impl<E> From<Report<E>> for DynamicReport<E> {
+    fn from(report: Report<E>) -> Self {
+        DynamicReport { report }
+    }
+}
+
+impl<E> Extend<Box<dyn Error + Send + Sync>> for DynamicReport<E> {
+    fn extend<T: IntoIterator<Item = Box<dyn Error + Send + Sync>>>(&mut self, iter: T) {
+        self.report.extra_causes.extend(iter);
+        self.report.source_count = OnceLock::new();
+    }
+}
+
+impl<E> fmt::Display for DynamicReport<E>
+where
+    E: Error,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.report, f)
+    }
+}
+
+impl<E> fmt::Debug for DynamicReport<E>
+where
+    DynamicReport<E>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::ReportStyle;
+use std::sync::OnceLock;
+
+/// The environment variable that selects the style of reports.
+const VAR: &str = "ERROR_REPORTER_STYLE";
+
+/// Returns the style selected by the `ERROR_REPORTER_STYLE` environment variable.
+///
+/// The variable is read the first time this function is called. Unknown values select the
+/// default style.
+pub(crate) fn env_style() -> &'static ReportStyle {
+    static STYLE: OnceLock<ReportStyle> = OnceLock::new();
+    STYLE.get_or_init(|| {
+        std::env::var(VAR)
+            .ok()
+            .and_then(|value| parse_style(&value))
+            .unwrap_or_default()
+    })
+}
+
+/// Parses the value of the `ERROR_REPORTER_STYLE` environment variable.
+pub(crate) fn parse_style(value: &str) -> Option<ReportStyle> {
+    let style = match value.trim() {
+        "compact" => ReportStyle::new(),
+        "pretty" => ReportStyle::new().pretty(true),
+        "json" => ReportStyle::new().json(true),
+        _ => return None,
+    };
+    Some(style)
+}
@@ -0,0 +1,105 @@
+use crate::Report;
+use std::error::Error;
+use std::fmt;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// An output format of a [`Report`] that is selected at compile time.
+///
+/// This trait is sealed and implemented by [`Dynamic`], [`SingleLine`], [`Pretty`], and
+/// [`Json`]. Use [`Report::into_format`] to select a format.
+pub trait ReportFormat: sealed::Sealed {
+    /// Whether the format is JSON.
+    #[doc(hidden)]
+    const JSON: bool = false;
+
+    #[doc(hidden)]
+    fn fmt<E>(report: &Report<E, Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        E: Error,
+        Self: Sized;
+}
+
+/// The default format that is selected at runtime by the options of the report.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Dynamic;
+
+/// Always format the report as a single line.
+///
+/// This is equivalent to calling [`Report::fmt_singleline`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SingleLine;
+
+/// Always format the report across multiple lines.
+///
+/// This is equivalent to calling [`Report::fmt_multiline`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Pretty;
+
+/// Format the report as a JSON object.
+///
+/// The object has a `message` field containing the message of the error and a `causes` field
+/// containing the messages of the causes.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{Json, Report};
+///
+/// let error = std::io::Error::other("could not load config");
+/// let cause = std::io::Error::other("permission denied");
+/// let report = Report::new(error)
+///     .with_causes(vec![Box::new(cause)])
+///     .into_format::<Json>();
+/// assert_eq!(
+///     report.to_string(),
+///     r#"{"message":"could not load config","causes":["permission denied"]}"#,
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Json;
+
+impl sealed::Sealed for Dynamic {}
+impl sealed::Sealed for SingleLine {}
+impl sealed::Sealed for Pretty {}
+impl sealed::Sealed for Json {}
+
+impl ReportFormat for Dynamic {
+    fn fmt<E>(report: &Report<E, Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        E: Error,
+    {
+        report.borrowed().fmt_dynamic(f)
+    }
+}
+
+impl ReportFormat for SingleLine {
+    fn fmt<E>(report: &Report<E, Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        E: Error,
+    {
+        report.fmt_singleline(f)
+    }
+}
+
+impl ReportFormat for Pretty {
+    fn fmt<E>(report: &Report<E, Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        E: Error,
+    {
+        report.fmt_multiline(f)
+    }
+}
+
+impl ReportFormat for Json {
+    const JSON: bool = true;
+
+    fn fmt<E>(report: &Report<E, Self>, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    where
+        E: Error,
+    {
+        report.borrowed().fmt_json(f)
+    }
+}
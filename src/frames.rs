@@ -0,0 +1,35 @@
+use crate::{Dynamic, Report, ReportFormat};
+use std::error::Error;
+use std::fmt;
+
+/// A [`Report`] whose causes are formatted by a closure.
+///
+/// This type is created by [`Report::format_frames`]. The message of the error is printed
+/// first, followed by the output of the closure for each cause. The closure is responsible for
+/// separating the causes from the preceding output. Causes for which the closure writes nothing
+/// are omitted.
+pub struct FramesReport<'a, E, C, F = Dynamic> {
+    report: &'a Report<E, F>,
+    format: C,
+}
+
+impl<'a, E, C, F> FramesReport<'a, E, C, F> {
+    pub(crate) fn new(report: &'a Report<E, F>, format: C) -> Self {
+        FramesReport { report, format }
+    }
+}
+
+impl<E, C, F> fmt::Display for FramesReport<'_, E, C, F>
+where
+    E: Error,
+    C: Fn(usize, &(dyn Error + 'static), &mut fmt::Formatter<'_>) -> fmt::Result,
+    F: ReportFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.report.borrowed().root())?;
+        for (index, error) in self.report.borrowed().sources().enumerate() {
+            (self.format)(index, error, f)?;
+        }
+        Ok(())
+    }
+}
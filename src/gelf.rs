@@ -0,0 +1,56 @@
+use crate::json::JsonString;
+use crate::{Report, ReportFormat};
+use std::error::Error;
+use std::fmt::Write;
+
+impl<E, F> Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    /// Format the report as a [GELF](https://go2docs.graylog.org/current/getting_in_log_data/gelf.html)
+    /// payload.
+    ///
+    /// The payload contains the message of the error as `short_message`, the formatted report as
+    /// `full_message`, and the message of each cause as an additional `_cause_N` field. The JSON
+    /// contains no insignificant whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(
+    ///     report.to_gelf_string("example.org", 3),
+    ///     concat!(
+    ///         r#"{"version":"1.1","host":"example.org","#,
+    ///         r#""short_message":"could not load config","#,
+    ///         r#""full_message":"could not load config: permission denied","#,
+    ///         r#""level":3,"_cause_0":"permission denied"}"#,
+    ///     ),
+    /// );
+    /// ```
+    pub fn to_gelf_string(&self, host: &str, level: u8) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            r#"{{"version":"1.1","host":{},"short_message":{},"full_message":{},"level":{level}"#,
+            JsonString(host),
+            JsonString(&self.borrowed().root().to_string()),
+            JsonString(&self.to_string()),
+        );
+        for cause in self.borrowed().causes() {
+            let _ = write!(
+                out,
+                r#","_cause_{}":{}"#,
+                cause.index,
+                JsonString(&cause.to_string()),
+            );
+        }
+        out.push('}');
+        out
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{AsSingleLine, Report, ReportFormat};
+use std::error::Error;
+use std::fmt::Write;
+
+impl<E, F> Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    /// Format the report as a GitHub Actions
+    /// [`error` workflow command](https://docs.github.com/en/actions/reference/workflow-commands-for-github-actions#setting-an-error-message).
+    ///
+    /// When this line is printed by a step of a workflow, GitHub shows the error as an
+    /// annotation, optionally attached to a `line` of a `file` in the repository. The chain is
+    /// always flattened into a single line, regardless of whether pretty-printing is enabled.
+    /// Newlines and other special characters are escaped as required by the workflow command
+    /// syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(
+    ///     report.to_github_annotation(Some("config.toml"), Some(3)),
+    ///     "::error file=config.toml,line=3::could not load config: permission denied",
+    /// );
+    /// assert_eq!(
+    ///     report.to_github_annotation(None, None),
+    ///     "::error::could not load config: permission denied",
+    /// );
+    /// ```
+    pub fn to_github_annotation(&self, file: Option<&str>, line: Option<u32>) -> String {
+        let mut out = String::from("::error");
+        let mut separator = ' ';
+        if let Some(file) = file {
+            out.push(separator);
+            out.push_str("file=");
+            escape(&mut out, file, true);
+            separator = ',';
+        }
+        if let Some(line) = line {
+            out.push(separator);
+            let _ = write!(out, "line={line}");
+        }
+        out.push_str("::");
+        escape(&mut out, &AsSingleLine(self.borrowed()).to_string(), false);
+        out
+    }
+}
+
+/// Escape `s` as the message or, if `property` is true, as a property of a workflow command.
+fn escape(out: &mut String, s: &str, property: bool) {
+    for c in s.chars() {
+        match c {
+            '%' => out.push_str("%25"),
+            '\r' => out.push_str("%0D"),
+            '\n' => out.push_str("%0A"),
+            ':' if property => out.push_str("%3A"),
+            ',' if property => out.push_str("%2C"),
+            _ => out.push(c),
+        }
+    }
+}
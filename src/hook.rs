@@ -0,0 +1,143 @@
+use std::any::TypeId;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// A function that formats reports, installed with [`set_hook`].
+pub type Hook = Box<dyn Fn(&dyn Error, &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync>;
+
+/// The hook installed with [`set_hook`].
+static HOOK: RwLock<Option<Arc<Hook>>> = RwLock::new(None);
+
+/// A function that formats errors of a specific type, installed with [`register_hook`].
+type TypeHook =
+    dyn Fn(&(dyn Error + 'static), &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync;
+
+/// The hooks installed with [`register_hook`].
+static TYPE_HOOKS: RwLock<Vec<TypeHookEntry>> = RwLock::new(Vec::new());
+
+/// A hook installed with [`register_hook`].
+struct TypeHookEntry {
+    type_id: TypeId,
+    is: fn(&(dyn Error + 'static)) -> bool,
+    hook: Arc<TypeHook>,
+}
+
+thread_local! {
+    /// Whether the hook is currently running on this thread.
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Install a hook that formats all reports.
+///
+/// Once a hook is installed, formatting a [`Report`](crate::Report) calls the hook with the
+/// error of the report instead of using the options of the report. This allows libraries to
+/// return reports while the application decides how they are presented.
+///
+/// Reports that are formatted by the hook itself are formatted without the hook. This allows
+/// the hook to fall back to the built-in formatting.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{set_hook, Report};
+///
+/// set_hook(Box::new(|error, f| {
+///     write!(f, "error: {:#}", Report::new(error))
+/// }));
+///
+/// let error = std::io::Error::other("could not load config");
+/// assert_eq!(Report::new(error).to_string(), "error: could not load config");
+/// ```
+pub fn set_hook(hook: Hook) {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(hook));
+}
+
+/// Formats `error` with the installed hook.
+///
+/// Returns `None` if no hook is installed or if the hook is already running on this thread.
+pub(crate) fn fmt_with_hook(error: &dyn Error, f: &mut fmt::Formatter<'_>) -> Option<fmt::Result> {
+    if IN_HOOK.get() {
+        return None;
+    }
+    let hook = HOOK
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()?;
+    IN_HOOK.set(true);
+    let _reset = Reset;
+    Some(hook(error, f))
+}
+
+/// Marks the hook as no longer running when dropped, even if the hook panics.
+struct Reset;
+
+impl Drop for Reset {
+    fn drop(&mut self) {
+        IN_HOOK.set(false);
+    }
+}
+
+/// Install a hook that formats the causes of type `T`.
+///
+/// Whenever a report prints a cause of type `T`, the hook is called instead of the `Display`
+/// implementation of `T`. Installing a second hook for the same type replaces the first one.
+/// The error of the report itself is always printed with its `Display` implementation since
+/// its type is not necessarily `'static`.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{register_hook, Report};
+///
+/// register_hook::<std::io::Error>(|error, f| write!(f, "{error} ({:?})", error.kind()));
+///
+/// let error = std::io::Error::other("could not load config");
+/// let cause = std::io::Error::from(std::io::ErrorKind::NotFound);
+/// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+/// assert_eq!(
+///     report.to_string(),
+///     "could not load config: entity not found (NotFound)",
+/// );
+/// ```
+pub fn register_hook<T>(
+    hook: impl Fn(&T, &mut fmt::Formatter<'_>) -> fmt::Result + Send + Sync + 'static,
+) where
+    T: Error + 'static,
+{
+    let entry = TypeHookEntry {
+        type_id: TypeId::of::<T>(),
+        is: |error| error.is::<T>(),
+        hook: Arc::new(move |error, f| match error.downcast_ref::<T>() {
+            Some(error) => hook(error, f),
+            None => write!(f, "{error}"),
+        }),
+    };
+    let mut hooks = TYPE_HOOKS.write().unwrap_or_else(PoisonError::into_inner);
+    hooks.retain(|e| e.type_id != entry.type_id);
+    hooks.push(entry);
+}
+
+/// Wrapper type for displaying a cause with the hook installed for its type.
+pub(crate) struct Hooked<'a> {
+    error: &'a (dyn Error + 'static),
+    hook: Option<Arc<TypeHook>>,
+}
+
+impl<'a> Hooked<'a> {
+    pub(crate) fn new(error: &'a (dyn Error + 'static)) -> Self {
+        let hooks = TYPE_HOOKS.read().unwrap_or_else(PoisonError::into_inner);
+        let hook = hooks.iter().find(|e| (e.is)(error)).map(|e| e.hook.clone());
+        Hooked { error, hook }
+    }
+}
+
+impl fmt::Display for Hooked<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.hook {
+            Some(hook) => hook(self.error, f),
+            None => fmt::Display::fmt(self.error, f),
+        }
+    }
+}
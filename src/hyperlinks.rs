@@ -0,0 +1,57 @@
+use crate::Report;
+use std::io::IsTerminal;
+
+impl<E, F> Report<E, F> {
+    /// Enable [hyperlinks](Report::hyperlinks) if stderr is a terminal that supports them.
+    ///
+    /// Support is detected with the same heuristics as the `supports-hyperlinks` crate:
+    ///
+    /// - If `FORCE_HYPERLINK` is set, hyperlinks are enabled unless it is `0`.
+    /// - Otherwise, hyperlinks are disabled if stderr is not a terminal.
+    /// - Otherwise, hyperlinks are enabled if the terminal is known to support them, which is
+    ///   determined from `DOMTERM`, `WT_SESSION`, `KONSOLE_VERSION`, `VTE_VERSION`,
+    ///   `TERM_PROGRAM`, and `TERM`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("see https://example.com");
+    /// eprintln!("{}", Report::new(error).hyperlinks_auto());
+    /// ```
+    pub fn hyperlinks_auto(self) -> Self {
+        let hyperlinks = should_hyperlink(std::io::stderr().is_terminal(), |name| {
+            std::env::var(name).ok()
+        });
+        self.hyperlinks(hyperlinks)
+    }
+}
+
+/// Returns whether hyperlinks should be used given the environment accessed via `var`.
+pub(crate) fn should_hyperlink(is_terminal: bool, var: impl Fn(&str) -> Option<String>) -> bool {
+    if let Some(force) = var("FORCE_HYPERLINK") {
+        return force != "0";
+    }
+    if !is_terminal {
+        return false;
+    }
+    if ["DOMTERM", "WT_SESSION", "KONSOLE_VERSION"]
+        .iter()
+        .any(|name| var(name).is_some())
+    {
+        return true;
+    }
+    if var("VTE_VERSION").is_some_and(|v| v.parse::<u32>().is_ok_and(|v| v >= 5000)) {
+        return true;
+    }
+    if var("TERM_PROGRAM").is_some_and(|p| {
+        matches!(
+            &*p,
+            "Hyper" | "iTerm.app" | "terminology" | "WezTerm" | "vscode" | "ghostty"
+        )
+    }) {
+        return true;
+    }
+    var("TERM").is_some_and(|t| matches!(&*t, "xterm-kitty" | "alacritty" | "xterm-ghostty"))
+}
@@ -0,0 +1,50 @@
+use crate::{Report, ReportFormat};
+use serde_json::Value;
+use std::error::Error;
+
+impl<E, F> Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    /// Convert the report to a [`serde_json::Value`].
+    ///
+    /// The value is an object with a `message` field containing the message of the error and a
+    /// `causes` field containing the messages of the causes. This is the same structure as the
+    /// output of the [`Json`](crate::Json) format.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    /// use serde_json::json;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(
+    ///     json!({ "status": 500, "error": report.to_json_value() }),
+    ///     json!({
+    ///         "status": 500,
+    ///         "error": {
+    ///             "message": "could not load config",
+    ///             "causes": ["permission denied"],
+    ///         },
+    ///     }),
+    /// );
+    /// ```
+    pub fn to_json_value(&self) -> Value {
+        let causes = self
+            .borrowed()
+            .causes()
+            .map(|cause| Value::String(cause.to_string()))
+            .collect();
+        let mut object = serde_json::Map::new();
+        object.insert(
+            "message".into(),
+            Value::String(self.borrowed().root().to_string()),
+        );
+        object.insert("causes".into(), Value::Array(causes));
+        Value::Object(object)
+    }
+}
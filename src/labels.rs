@@ -0,0 +1,74 @@
+use crate::Report;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+/// Provides the labels that a [`Report`] prints in addition to the messages of the errors.
+///
+/// This can be used to localize reports, for example by looking up the labels in a Fluent
+/// bundle. Each method defaults to the built-in English label.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{Labels, Report};
+/// use std::borrow::Cow;
+///
+/// struct German;
+///
+/// impl Labels for German {
+///     fn caused_by(&self) -> Cow<'_, str> {
+///         "Verursacht durch:".into()
+///     }
+///
+///     fn elided(&self, count: usize) -> Cow<'_, str> {
+///         format!("... und {count} weitere").into()
+///     }
+/// }
+///
+/// let error = std::io::Error::other("Konfiguration konnte nicht geladen werden");
+/// let causes = vec![
+///     Box::new(std::io::Error::other("Datei konnte nicht geöffnet werden")) as _,
+///     Box::new(std::io::Error::other("Zugriff verweigert")) as _,
+/// ];
+/// let report = Report::new(error)
+///     .with_causes(causes)
+///     .pretty(true)
+///     .max_causes(1)
+///     .labels(German);
+/// assert_eq!(
+///     report.to_string(),
+///     "Konfiguration konnte nicht geladen werden\n\n\
+///      Verursacht durch:\n   \
+///         0: Datei konnte nicht geöffnet werden\n      \
+///         ... und 1 weitere",
+/// );
+/// ```
+pub trait Labels: Send + Sync {
+    /// Returns the header printed before the causes in pretty mode.
+    ///
+    /// This takes precedence over [`Report::caused_by_label`].
+    fn caused_by(&self) -> Cow<'_, str> {
+        Cow::Borrowed("Caused by:")
+    }
+
+    /// Returns the message printed instead of `count` causes that exceed the
+    /// [maximum number of causes](Report::max_causes).
+    fn elided(&self, count: usize) -> Cow<'_, str> {
+        Cow::Owned(format!("... and {count} more"))
+    }
+}
+
+impl fmt::Debug for dyn Labels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Labels")
+    }
+}
+
+impl<E, F> Report<E, F> {
+    /// Replace the built-in English labels.
+    pub fn labels(mut self, labels: impl Labels + 'static) -> Self {
+        self.options.labels = Some(Arc::new(labels));
+        self
+    }
+}
@@ -2,14 +2,95 @@
 //!
 //! The code in this crate is copied from [std::error::Report] which is unstable.
 //! Unlike the code in std, this code does not support printing backtrace because doing so relies on other unstable features.
+//! In particular, backtraces are retrieved from errors via `std::error::request_ref` which requires the
+//! unstable `error_generic_member_access` feature.
+//! For the same reason, reports cannot print any other data that errors expose via `Error::provide`,
+//! such as timestamps or durations.
 //! Otherwise this code is identical to the code in std as of 2023-03-14.
 
+mod adapters;
+#[cfg(feature = "anyhow")]
+mod anyhow;
+mod chained;
+#[cfg(feature = "color_auto")]
+mod color;
+#[cfg(feature = "heapless")]
+mod compact;
+mod description;
+mod diagnostic;
+mod dynamic;
+#[cfg(feature = "env_style")]
+mod env;
+mod format;
+mod frames;
+#[cfg(feature = "gelf")]
+mod gelf;
+#[cfg(feature = "github_actions")]
+mod github;
+mod hook;
+#[cfg(feature = "hyperlinks_auto")]
+mod hyperlinks;
+mod json;
+#[cfg(feature = "serde_json")]
+mod json_value;
+#[cfg(feature = "i18n")]
+mod labels;
+mod lines;
+#[cfg(feature = "miette")]
+mod miette;
+mod optional;
+mod options;
+#[cfg(feature = "paginate")]
+mod paginate;
+#[cfg(feature = "rfc7807")]
+mod rfc7807;
+mod section;
+#[cfg(feature = "slog")]
+mod slog;
+mod strategy;
+mod style;
+#[cfg(feature = "terminal_size")]
+mod terminal;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
+mod wrap;
 
+pub use adapters::{WriteFmt, WriteIo};
+pub use chained::{ChainedError, TryFromStrError};
+pub use description::ErrorDescription;
+pub use diagnostic::DiagnosticCode;
+pub use dynamic::DynamicReport;
+pub use format::{Dynamic, Json, Pretty, ReportFormat, SingleLine};
+pub use frames::FramesReport;
+use hook::Hooked;
+pub use hook::{register_hook, set_hook, Hook};
+use json::JsonString;
+#[cfg(feature = "i18n")]
+pub use labels::Labels;
+pub use lines::LinesReport;
+pub use optional::OptionalError;
+pub use options::{BoxStyle, CauseNumbering, CycleBehavior, FormatOptions, Theme};
+use options::{CauseType, BUILTIN_CAUSE_TYPES};
+#[cfg(feature = "paginate")]
+pub use paginate::paginate;
+pub use section::CauseSection;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Write;
+use std::io::{self, IsTerminal};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::OnceLock;
+use strategy::Strategy;
+pub use strategy::{CauseStrategy, DefaultStrategy};
+pub use style::{set_default_style, ReportStyle};
+#[cfg(feature = "tracing")]
+pub use tracing_layer::ReportLayer;
+use wrap::{escape_len, visible_width, width_of, Wrapped};
 
 /// An error reporter that prints an error and its sources.
 ///
@@ -215,11 +296,21 @@ use std::fmt::Write;
 /// Caused by:
 ///       SuperErrorSideKick is here!
 /// ```
-pub struct Report<E = Box<dyn Error>> {
+pub struct Report<E = Box<dyn Error>, F = Dynamic> {
     /// The error being reported.
     error: E,
-    /// Whether the report should be pretty-printed.
-    pretty: bool,
+    /// The options that control how the report is formatted.
+    options: FormatOptions,
+    /// Additional causes that are printed after the sources of the error.
+    extra_causes: Vec<Box<dyn Error + Send + Sync + 'static>>,
+    /// Messages that replace the messages of the causes, keyed by the index of the cause.
+    replacements: HashMap<usize, String>,
+    /// The strategy used to collect the causes of the error, if it is not the default.
+    strategy: Option<Strategy<E>>,
+    /// The output format selected at compile time.
+    format: PhantomData<F>,
+    /// The cached result of [`Report::source_count`].
+    source_count: OnceLock<usize>,
 }
 
 impl<E> Report<E>
@@ -232,7 +323,7 @@ where
     }
 }
 
-impl<E> Report<E> {
+impl<E, F> Report<E, F> {
     /// Enable pretty-printing the report across multiple lines.
     ///
     /// # Examples
@@ -339,93 +430,3387 @@ impl<E> Report<E> {
     ///    1: SuperErrorSideKickSideKick is here!
     /// ```
     pub fn pretty(mut self, pretty: bool) -> Self {
-        self.pretty = pretty;
+        self.options.pretty = pretty;
         self
     }
+
+    /// Enable pretty-printing if stderr is a terminal.
+    ///
+    /// Interactive users get the more readable multi-line output, while reports that are
+    /// written to a file or pipe stay on a single line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// eprintln!("{}", Report::new(error).pretty_auto());
+    /// ```
+    pub fn pretty_auto(self) -> Self {
+        let pretty = io::stderr().is_terminal();
+        self.pretty(pretty)
+    }
+
+    /// Enable or disable JSON output.
+    ///
+    /// The report is formatted like a report with the [`Json`] format. This takes precedence
+    /// over all other layouts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let report = Report::new(error).json(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     r#"{"message":"could not load config","causes":[]}"#,
+    /// );
+    /// ```
+    pub fn json(mut self, json: bool) -> Self {
+        self.options.json = json;
+        self
+    }
+
+    /// Set all formatting options at once.
+    ///
+    /// This replaces any options that were set previously.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::{FormatOptions, Report};
+    ///
+    /// let mut options = FormatOptions::default();
+    /// options.pretty = true;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let report = Report::new(error).options(options.clone());
+    /// ```
+    pub fn options(mut self, options: FormatOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns a reference to the error.
+    ///
+    /// Use this to access the concrete error type. `Report` also dereferences to `dyn Error`,
+    /// which is more convenient when only the methods of [`Error`] are needed.
+    pub fn inner(&self) -> &E {
+        &self.error
+    }
+
+    /// Recover the error from the report.
+    ///
+    /// This is useful to propagate the error after it has been printed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// fn load_config() -> Result<(), std::io::Error> {
+    ///     Err(std::io::Error::other("could not load config"))
+    /// }
+    ///
+    /// fn run() -> Result<(), std::io::Error> {
+    ///     match load_config() {
+    ///         Ok(()) => Ok(()),
+    ///         Err(e) => {
+    ///             let report = Report::new(e);
+    ///             eprintln!("Error: {report}");
+    ///             Err(report.into_err())
+    ///         }
+    ///     }
+    /// }
+    /// # assert!(run().is_err());
+    /// ```
+    pub fn into_err(self) -> E {
+        self.error
+    }
+
+    /// Select the output format at compile time.
+    ///
+    /// The default format [`Dynamic`] is selected at runtime by the options of the report.
+    /// [`SingleLine`], [`Pretty`], and [`Json`] always produce the same kind of output and ignore
+    /// [`Report::pretty`], which avoids the runtime dispatch. All other options are preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::{Pretty, Report};
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report: Report<_, Pretty> = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .into_format();
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n      permission denied",
+    /// );
+    /// ```
+    pub fn into_format<G>(self) -> Report<E, G> {
+        Report {
+            error: self.error,
+            options: self.options,
+            extra_causes: self.extra_causes,
+            replacements: self.replacements,
+            strategy: self.strategy,
+            format: PhantomData,
+            source_count: self.source_count,
+        }
+    }
+
+    /// Draw the sources as a tree when pretty-printing.
+    ///
+    /// This has no effect unless pretty-printing is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    /// # use std::error::Error;
+    /// # use std::fmt;
+    /// # #[derive(Debug)]
+    /// # struct SuperError {
+    /// #     source: SuperErrorSideKick,
+    /// # }
+    /// # impl fmt::Display for SuperError {
+    /// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// #         write!(f, "SuperError is here!")
+    /// #     }
+    /// # }
+    /// # impl Error for SuperError {
+    /// #     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    /// #         Some(&self.source)
+    /// #     }
+    /// # }
+    /// # #[derive(Debug)]
+    /// # struct SuperErrorSideKick {
+    /// #     source: SuperErrorSideKickSideKick,
+    /// # }
+    /// # impl fmt::Display for SuperErrorSideKick {
+    /// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// #         write!(f, "SuperErrorSideKick is here!")
+    /// #     }
+    /// # }
+    /// # impl Error for SuperErrorSideKick {
+    /// #     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    /// #         Some(&self.source)
+    /// #     }
+    /// # }
+    /// # #[derive(Debug)]
+    /// # struct SuperErrorSideKickSideKick;
+    /// # impl fmt::Display for SuperErrorSideKickSideKick {
+    /// #     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    /// #         write!(f, "SuperErrorSideKickSideKick is here!")
+    /// #     }
+    /// # }
+    /// # impl Error for SuperErrorSideKickSideKick { }
+    ///
+    /// let source = SuperErrorSideKickSideKick;
+    /// let source = SuperErrorSideKick { source };
+    /// let error = SuperError { source };
+    /// let report = Report::new(error).pretty(true).tree(true);
+    /// eprintln!("Error: {report:?}");
+    /// ```
+    ///
+    /// This example produces the following output:
+    ///
+    /// ```console
+    /// Error: SuperError is here!
+    /// ├── SuperErrorSideKick is here!
+    /// └── SuperErrorSideKickSideKick is here!
+    /// ```
+    pub fn tree(mut self, tree: bool) -> Self {
+        self.options.tree = tree;
+        self
+    }
+
+    /// Set the characters used to draw the tree.
+    ///
+    /// The default is [`BoxStyle::Unicode`].
+    pub fn box_style(mut self, box_style: BoxStyle) -> Self {
+        self.options.box_style = box_style;
+        self
+    }
+
+    /// Group the causes in parentheses in single-line mode.
+    ///
+    /// The causes are printed after the error as `(caused by: cause1; cause2)`. This replaces
+    /// the [separator](Self::separator).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let causes = vec![
+    ///     Box::new(std::io::Error::other("could not open config.toml")) as _,
+    ///     Box::new(std::io::Error::other("permission denied")) as _,
+    /// ];
+    /// let report = Report::new(error).with_causes(causes).parenthesized(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config (caused by: could not open config.toml; permission denied)",
+    /// );
+    /// ```
+    pub fn parenthesized(mut self, parenthesized: bool) -> Self {
+        self.options.parenthesized = parenthesized;
+        self
+    }
+
+    /// Enable or disable Cargo-style output.
+    ///
+    /// The report is formatted like the errors printed by Cargo: the message of the error is
+    /// preceded by `error: ` and each cause is printed in its own section with a
+    /// [header](Self::caused_by_label) and an indentation of two spaces. This takes precedence
+    /// over all layouts except JSON, regardless of whether pretty-printing is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("failed to load manifest");
+    /// let causes = vec![
+    ///     Box::new(std::io::Error::other("failed to read `Cargo.toml`")) as _,
+    ///     Box::new(std::io::Error::other("permission denied")) as _,
+    /// ];
+    /// let report = Report::new(error).with_causes(causes).cargo_style(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "\
+    /// error: failed to load manifest
+    ///
+    /// Caused by:
+    ///   failed to read `Cargo.toml`
+    ///
+    /// Caused by:
+    ///   permission denied",
+    /// );
+    /// ```
+    pub fn cargo_style(mut self, cargo_style: bool) -> Self {
+        self.options.cargo_style = cargo_style;
+        self
+    }
+
+    /// Set a footer that is printed after the causes in pretty mode.
+    ///
+    /// The footer is separated from the causes by a blank line. It is printed even if the error
+    /// has no causes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .footer("Run with --verbose for more information.");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "\
+    /// could not load config
+    ///
+    /// Caused by:
+    ///       permission denied
+    ///
+    /// Run with --verbose for more information.",
+    /// );
+    /// ```
+    pub fn footer(mut self, footer: impl Into<Cow<'static, str>>) -> Self {
+        self.options.footer = Some(footer.into());
+        self
+    }
+
+    /// Set a [footer](Self::footer) that asks the user to report the error at `url`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("internal error");
+    /// let report = Report::new(error)
+    ///     .pretty(true)
+    ///     .bug_report_url("https://example.org/issues");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "\
+    /// internal error
+    ///
+    /// If you believe this is a bug, please report it at https://example.org/issues",
+    /// );
+    /// ```
+    pub fn bug_report_url(self, url: &str) -> Self {
+        self.footer(format!(
+            "If you believe this is a bug, please report it at {url}"
+        ))
+    }
+
+    /// Set the name of the program that is printed before the report, separated by `: `.
+    ///
+    /// This follows the convention of `err(3)` and GNU tools, which prefix diagnostics with the
+    /// name of the program. The name is printed before the [title](Self::title_prefix), if any,
+    /// and in all layouts except JSON. Use [`Report::program_name_from_args`] to use the name
+    /// that the program was invoked with.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("failed to open file");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .program_name("myprog");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "myprog: failed to open file: permission denied",
+    /// );
+    /// ```
+    pub fn program_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.options.program_name = Some(name.into());
+        self
+    }
+
+    /// Set the [program name](Self::program_name) to the file name of the first command-line
+    /// argument.
+    ///
+    /// If the program was invoked without arguments, no name is printed.
+    pub fn program_name_from_args(mut self) -> Self {
+        self.options.program_name = std::env::args_os().next().map(|arg| {
+            let path = std::path::Path::new(&arg);
+            let name = path.file_name().unwrap_or(path.as_os_str());
+            Cow::Owned(name.to_string_lossy().into_owned())
+        });
+        self
+    }
+
+    /// Set a title that is printed before the report, separated by `: `.
+    ///
+    /// This replaces formatting the report with `"Error: {report}"` and also applies when the
+    /// report is formatted with [`Debug`](fmt::Debug), for example when it is returned from
+    /// `main`. The title is styled like the [header](Theme::header). It is not printed in JSON,
+    /// Cargo-style, or rustc-style output.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .title_prefix("Error");
+    /// assert_eq!(
+    ///     format!("{report:?}"),
+    ///     "Error: could not load config: permission denied",
+    /// );
+    /// ```
+    pub fn title_prefix(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.options.title_prefix = Some(title.into());
+        self
+    }
+
+    /// Enable or disable Markdown output for chat messages.
+    ///
+    /// The message of the error is printed in bold and the causes are printed in a code block
+    /// below it, which renders well in Slack, Discord, and similar chat services. Markdown
+    /// characters in the message of the error are escaped. This takes precedence over all
+    /// layouts except JSON, Cargo-style, and rustc-style, regardless of whether pretty-printing
+    /// is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config_file");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .markdown(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "**could not load config\\_file**\n```\nCaused by:\n      permission denied\n```",
+    /// );
+    /// ```
+    pub fn markdown(mut self, markdown: bool) -> Self {
+        self.options.markdown = markdown;
+        self
+    }
+
+    /// Enable or disable rustc-style output.
+    ///
+    /// The report is formatted like the diagnostics printed by rustc: the message of the error is
+    /// preceded by `error: `, or by `error[CODE]: ` if an [error code](Self::error_code) is set,
+    /// and each cause is printed as a `= note:` line. Use [`Report::diagnostic_code`] to take the
+    /// code from an error that implements [`DiagnosticCode`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not compile `app`");
+    /// let causes = vec![
+    ///     Box::new(std::io::Error::other("failed to run custom build command")) as _,
+    ///     Box::new(std::io::Error::other("linker not found")) as _,
+    /// ];
+    /// let report = Report::new(error)
+    ///     .with_causes(causes)
+    ///     .rustc_style(true)
+    ///     .error_code("E0463");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "\
+    /// error[E0463]: could not compile `app`
+    ///   = note: failed to run custom build command
+    ///   = note: linker not found",
+    /// );
+    /// ```
+    pub fn rustc_style(mut self, rustc_style: bool) -> Self {
+        self.options.rustc_style = rustc_style;
+        self
+    }
+
+    /// Set the code printed in the header of [rustc-style](Self::rustc_style) reports.
+    pub fn error_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.options.error_code = Some(code.into());
+        self
+    }
+
+    /// Enable or disable plain output in pretty mode.
+    ///
+    /// Each cause is printed on its own line behind the [header](Self::caused_by_label),
+    /// without numbers, indentation, or blank lines. This is easier to follow with a screen
+    /// reader than the aligned columns of the default layout. This option has no effect in
+    /// tree and nested mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let causes = vec![
+    ///     Box::new(std::io::Error::other("could not open config.toml")) as _,
+    ///     Box::new(std::io::Error::other("permission denied")) as _,
+    /// ];
+    /// let report = Report::new(error)
+    ///     .with_causes(causes)
+    ///     .pretty(true)
+    ///     .plain(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\
+    ///      Caused by: could not open config.toml\n\
+    ///      Caused by: permission denied",
+    /// );
+    /// ```
+    pub fn plain(mut self, plain: bool) -> Self {
+        self.options.plain = plain;
+        self
+    }
+
+    /// Draw a panel with the given title around the report.
+    ///
+    /// The panel is drawn with the characters of the [box style](Self::box_style). If a
+    /// [wrap width](Self::wrap_at) is set, it includes the frame of the panel. The title can be
+    /// empty. Line breaks in the title are replaced by spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .panel("Error");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "\
+    /// ╭─ Error ─────────────────╮
+    /// │ could not load config   │
+    /// │                         │
+    /// │ Caused by:              │
+    /// │       permission denied │
+    /// ╰─────────────────────────╯",
+    /// );
+    /// ```
+    pub fn panel(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.options.panel = Some(title.into());
+        self
+    }
+
+    /// Enable or disable nested output in pretty mode.
+    ///
+    /// Each cause is printed one level deeper than the error it is a source of, behind the
+    /// corner character of the [box style](Self::box_style). This option has no effect in tree
+    /// mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let causes = vec![
+    ///     Box::new(std::io::Error::other("could not open config.toml")) as _,
+    ///     Box::new(std::io::Error::other("permission denied")) as _,
+    /// ];
+    /// let report = Report::new(error)
+    ///     .with_causes(causes)
+    ///     .pretty(true)
+    ///     .nested(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n└── could not open config.toml\n    └── permission denied",
+    /// );
+    /// ```
+    pub fn nested(mut self, nested: bool) -> Self {
+        self.options.nested = nested;
+        self
+    }
+
+    /// Append additional causes to the report.
+    ///
+    /// The causes are printed after the sources returned by [`Error::source`], in the order in
+    /// which they were added. This is useful when the source chain of a wrapper error does not
+    /// expose the actual cause. The causes do not replace the natural source chain; use
+    /// [`Report::with_strategy`] to select the causes of the error instead.
+    ///
+    /// The causes must be `Send + Sync` so that the report remains `Send + Sync` whenever `E` is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(report.to_string(), "could not load config: permission denied");
+    /// ```
+    pub fn with_causes(
+        mut self,
+        extra_causes: Vec<Box<dyn Error + Send + Sync + 'static>>,
+    ) -> Self {
+        self.extra_causes.extend(extra_causes);
+        self.source_count = OnceLock::new();
+        self
+    }
+
+    /// Print the [`ErrorKind`](std::io::ErrorKind) of causes that are `std::io::Error`s.
+    ///
+    /// The kind is appended in parentheses after the message of the cause. It is appended even
+    /// if the message already contains the kind.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    /// use std::io::ErrorKind;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::from(ErrorKind::PermissionDenied);
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .show_io_error_kind(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config: permission denied (PermissionDenied)",
+    /// );
+    /// ```
+    pub fn show_io_error_kind(mut self, show: bool) -> Self {
+        self.options.show_io_error_kind = show;
+        self
+    }
+
+    /// Pretty-print the report as HTML, with the sources in an ordered list.
+    ///
+    /// All messages are HTML-escaped. This has no effect unless pretty-printing is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load <config>");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .html_list(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "<p>could not load &lt;config&gt;</p><ol><li>permission denied</li></ol>",
+    /// );
+    /// ```
+    pub fn html_list(mut self, html_list: bool) -> Self {
+        self.options.html_list = html_list;
+        self
+    }
+
+    /// Set when the causes are numbered when pretty-printing.
+    ///
+    /// The default is [`CauseNumbering::Auto`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::{CauseNumbering, Report};
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .cause_numbering(CauseNumbering::Always);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n   0: permission denied",
+    /// );
+    /// ```
+    pub fn cause_numbering(mut self, cause_numbering: CauseNumbering) -> Self {
+        self.options.cause_numbering = cause_numbering;
+        self
+    }
+
+    /// Number the causes even if there is only one.
+    ///
+    /// This is a shorthand for [`Report::cause_numbering`] with [`CauseNumbering::Always`] if
+    /// `always_number` is `true` and [`CauseNumbering::Auto`] otherwise.
+    pub fn always_number(self, always_number: bool) -> Self {
+        if always_number {
+            self.cause_numbering(CauseNumbering::Always)
+        } else {
+            self.cause_numbering(CauseNumbering::Auto)
+        }
+    }
+
+    /// Set a string that is printed before each cause in single-line mode.
+    ///
+    /// The default is the empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .cause_prefix("source: ");
+    /// assert_eq!(report.to_string(), "could not load config: source: permission denied");
+    /// ```
+    pub fn cause_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.options.cause_prefix = prefix.into();
+        self
+    }
+
+    /// Set the indentation of the causes when pretty-printing.
+    ///
+    /// The indentation consists of `width` copies of `fill`, for example a single `\t` for
+    /// tab-indented output. It is printed before causes that are not numbered and before the
+    /// continuation lines of all causes. By default, the indentation consists of spaces and
+    /// aligns continuation lines with the first line of each cause.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied\nby policy");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .indentation(2, "> ");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n> > permission denied\n> > by policy",
+    /// );
+    /// ```
+    pub fn indentation(mut self, width: usize, fill: &str) -> Self {
+        self.options.indentation = Some(Cow::Owned(fill.repeat(width)));
+        self
+    }
+
+    /// Set the string that separates the messages in single-line mode.
+    ///
+    /// The default is `: `. A different separator can make the output easier to read if the
+    /// messages themselves contain colons. A connective word such as ` because ` turns the
+    /// report into a sentence, which can be more suitable for messages shown to end users.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("C:\\config.toml: permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .separator(" -> ");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config -> C:\\config.toml: permission denied",
+    /// );
+    ///
+    /// let error = std::io::Error::other("failed to load config");
+    /// let cause = std::io::Error::other("permission was denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .separator(" because ");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "failed to load config because permission was denied",
+    /// );
+    /// ```
+    pub fn separator(mut self, separator: impl Into<Cow<'static, str>>) -> Self {
+        self.options.separator = separator.into();
+        self
+    }
+
+    /// Set the number of the first cause when pretty-printing.
+    ///
+    /// The default is 0. Numbers do not exceed `usize::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause1 = std::io::Error::other("could not open file");
+    /// let cause2 = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause1), Box::new(cause2)])
+    ///     .pretty(true)
+    ///     .number_from(1);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n   1: could not open file\n   2: permission denied",
+    /// );
+    /// ```
+    pub fn number_from(mut self, first: usize) -> Self {
+        self.options.first_number = first;
+        self
+    }
+
+    /// Set the string that replaces line breaks in messages in single-line mode.
+    ///
+    /// The default is `; `, which guarantees that the single-line format produces a single line.
+    /// If `replacement` is `None`, line breaks are printed unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("line 1: invalid key\nline 2: invalid value");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config: line 1: invalid key; line 2: invalid value",
+    /// );
+    /// let report = report.newline_replacement(Some(" / ".into()));
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config: line 1: invalid key / line 2: invalid value",
+    /// );
+    /// ```
+    pub fn newline_replacement(mut self, replacement: Option<Cow<'static, str>>) -> Self {
+        self.options.newline_replacement = replacement;
+        self
+    }
+
+    /// Set the minimum width of the cause numbers when pretty-printing.
+    ///
+    /// Numbers are right-aligned in a column that is at least `digits` characters wide. The
+    /// column grows if the chain has more causes than fit into it. Continuation lines are
+    /// indented to match the column. The default is 4.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause1 = std::io::Error::other("could not open file");
+    /// let cause2 = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause1), Box::new(cause2)])
+    ///     .pretty(true)
+    ///     .min_number_digits(2);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n 0: could not open file\n 1: permission denied",
+    /// );
+    /// ```
+    pub fn min_number_digits(mut self, digits: usize) -> Self {
+        self.options.min_number_digits = digits;
+        self
+    }
+
+    /// Set the string printed between a cause number and the cause when pretty-printing.
+    ///
+    /// The default is `": "`. Continuation lines are indented to match the separator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause1 = std::io::Error::other("could not open file");
+    /// let cause2 = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause1), Box::new(cause2)])
+    ///     .pretty(true)
+    ///     .number_separator(". ");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n   0. could not open file\n   1. permission denied",
+    /// );
+    /// ```
+    pub fn number_separator(mut self, separator: impl Into<Cow<'static, str>>) -> Self {
+        self.options.number_separator = separator.into();
+        self
+    }
+
+    /// Set the header that is printed before the causes when pretty-printing.
+    ///
+    /// The default is `Caused by:`. The indentation of the causes does not depend on the header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("Konfiguration konnte nicht geladen werden");
+    /// let cause = std::io::Error::other("Zugriff verweigert");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .caused_by_label("Verursacht durch:");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "Konfiguration konnte nicht geladen werden\n\nVerursacht durch:\n      Zugriff verweigert",
+    /// );
+    /// ```
+    pub fn caused_by_label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.options.caused_by_label = label.into();
+        self
+    }
+
+    /// Set a prefix that is printed at the start of every line.
+    ///
+    /// This keeps multi-line reports attributable when they are interleaved with the output of
+    /// other processes. The prefix is not included in the [wrap width](Self::wrap_at) but in
+    /// the [maximum length](Self::max_len).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .line_prefix("my-app: ");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "my-app: could not load config\n\
+    ///      my-app: \n\
+    ///      my-app: Caused by:\n\
+    ///      my-app:       permission denied",
+    /// );
+    /// ```
+    pub fn line_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.options.line_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Enable or disable hyperlinks.
+    ///
+    /// If enabled, `http://` and `https://` URLs in messages are printed as
+    /// [OSC 8](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda) hyperlinks,
+    /// which many terminals make clickable. Terminals that do not support them usually print
+    /// only the URL, but some print the escape sequences verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("see https://example.com.");
+    /// let report = Report::new(error).hyperlinks(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "see \x1b]8;;https://example.com\x1b\\https://example.com\x1b]8;;\x1b\\.",
+    /// );
+    /// ```
+    pub fn hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.options.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Enable or disable CRLF line breaks.
+    ///
+    /// If enabled, every line break in the output is printed as `\r\n` instead of `\n`. This
+    /// is required by some protocols and log collectors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .crlf(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\r\n\r\nCaused by:\r\n      permission denied",
+    /// );
+    /// ```
+    pub fn crlf(mut self, crlf: bool) -> Self {
+        self.options.crlf = crlf;
+        self
+    }
+
+    /// Enable or disable the blank line before the causes when pretty-printing.
+    ///
+    /// This is enabled by default. Disabling it can be useful if the report is written to a
+    /// log that treats blank lines as record separators.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .blank_line_before_causes(false);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\nCaused by:\n      permission denied",
+    /// );
+    /// ```
+    pub fn blank_line_before_causes(mut self, blank_line: bool) -> Self {
+        self.options.blank_line_before_causes = blank_line;
+        self
+    }
+
+    /// Set the template of the first line when pretty-printing.
+    ///
+    /// `{message}` in the template is replaced by the message of the error. All other text is
+    /// printed as is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let report = Report::new(error)
+    ///     .pretty(true)
+    ///     .header_template("[ERROR] {message}");
+    /// assert_eq!(report.to_string(), "[ERROR] could not load config");
+    /// ```
+    pub fn header_template(mut self, template: impl Into<Cow<'static, str>>) -> Self {
+        self.options.header_template = Some(template.into());
+        self
+    }
+
+    /// Set the template of the cause lines when pretty-printing.
+    ///
+    /// `{index}` in the template is replaced by the number of the cause and `{message}` by its
+    /// message. All other text is printed as is. The template replaces the numbering,
+    /// indentation, and bullets of the causes. The header before the causes is still set with
+    /// [`Report::caused_by_label`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .caused_by_label("Causes:")
+    ///     .cause_template("  [{index}] {message}");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCauses:\n  [0] permission denied",
+    /// );
+    /// ```
+    pub fn cause_template(mut self, template: impl Into<Cow<'static, str>>) -> Self {
+        self.options.cause_template = Some(template.into());
+        self
+    }
+
+    /// Print the type names of the causes.
+    ///
+    /// When pretty-printing, the type name is printed in parentheses after the cause number.
+    /// Otherwise, it is printed in angle brackets before the cause.
+    ///
+    /// The type of a `dyn Error` cannot be inspected at runtime, so only the types that are known
+    /// to the report can be named. These are the error types of the standard library and types
+    /// registered with [`Report::register_cause_type`]. The names are those returned by
+    /// [`std::any::type_name`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = "x".parse::<i32>().unwrap_err();
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .show_cause_types(true);
+    /// let name = std::any::type_name::<std::num::ParseIntError>();
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     format!("could not load config: <{name}>invalid digit found in string"),
+    /// );
+    /// ```
+    pub fn show_cause_types(mut self, show: bool) -> Self {
+        self.options.show_cause_types = show;
+        self
+    }
+
+    /// Register a type whose name is printed by [`Report::show_cause_types`].
+    pub fn register_cause_type<T>(mut self) -> Self
+    where
+        T: Error + 'static,
+    {
+        self.options.cause_types.push(CauseType::of::<T>());
+        self
+    }
+
+    /// Set what happens when the source chain forms a cycle.
+    ///
+    /// A source chain forms a cycle if [`Error::source`] eventually returns an error that was
+    /// already part of the chain. Without cycle detection, formatting such a report would never
+    /// terminate. The default is to print `<cycle detected>` after the last cause.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::{CycleBehavior, Report};
+    ///
+    /// # let error = std::io::Error::other("could not load config");
+    /// let report = Report::new(error).on_cycle(CycleBehavior::Annotate("<cycle>".into()));
+    /// ```
+    pub fn on_cycle(mut self, behavior: CycleBehavior) -> Self {
+        self.options.on_cycle = behavior;
+        self
+    }
+
+    /// Upper-case the first character of the error and of each cause.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("ärger");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .capitalize_messages(true);
+    /// assert_eq!(report.to_string(), "Could not load config: Ärger");
+    /// ```
+    pub fn capitalize_messages(mut self, capitalize: bool) -> Self {
+        self.options.capitalize_messages = capitalize;
+        self
+    }
+
+    /// Print the causes as a bullet list when pretty-printing.
+    ///
+    /// The bullets replace the cause numbers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause1 = std::io::Error::other("could not open file");
+    /// let cause2 = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause1), Box::new(cause2)])
+    ///     .pretty(true)
+    ///     .use_bullet_list(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n  • could not open file\n  • permission denied",
+    /// );
+    /// ```
+    pub fn use_bullet_list(mut self, use_bullet_list: bool) -> Self {
+        self.options.use_bullet_list = use_bullet_list;
+        self
+    }
+
+    /// Set the bullet used by [`Report::use_bullet_list`].
+    ///
+    /// The default is `•`. Symbols such as `✗` or `↳` can be used to match the iconography of
+    /// a CLI. If the box style is [`BoxStyle::Ascii`], non-ASCII bullets are replaced by `-`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .use_bullet_list(true)
+    ///     .bullet('↳');
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n  ↳ permission denied",
+    /// );
+    /// ```
+    pub fn bullet(mut self, bullet: char) -> Self {
+        self.options.bullet = bullet;
+        self
+    }
+
+    /// Wrap the messages at the given column when pretty-printing.
+    ///
+    /// Lines are only broken at spaces. Continuation lines are indented like the first line of
+    /// the message.
+    ///
+    /// With the `unicode-width` feature, widths are measured in terminal columns. Otherwise,
+    /// every character is assumed to be one column wide.
+    ///
+    /// The width of the formatter takes precedence over this setting. For example,
+    /// `format!("{report:60}")` wraps pretty-printed reports at column 60. Single-line reports
+    /// are padded to the width of the formatter instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("the file could not be opened");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .wrap_at(24);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\nCaused by:\n      the file could not\n      be opened",
+    /// );
+    /// ```
+    pub fn wrap_at(mut self, width: usize) -> Self {
+        self.options.wrap_width = Some(width);
+        self
+    }
+
+    /// Style the report with ANSI escape sequences.
+    ///
+    /// The styles are taken from the [`Theme`] set with [`Report::theme`]. HTML lists are never
+    /// styled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .colored(true);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\n\x1b[1mCaused by:\x1b[0m\n      permission denied",
+    /// );
+    /// ```
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.options.colored = colored;
+        self
+    }
+
+    /// Set the styles used by [`Report::colored`].
+    ///
+    /// Besides the default theme, [`Theme`] provides presets for [dark](Theme::dark) and
+    /// [light](Theme::light) backgrounds, for [24-bit colors](Theme::truecolor), and for
+    /// terminals that only support the [16 basic colors](Theme::basic).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::{Report, Theme};
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true)
+    ///     .colored(true)
+    ///     .theme(Theme::basic());
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config\n\n\x1b[1;31mCaused by:\x1b[0m\n      permission denied",
+    /// );
+    /// ```
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.options.theme = theme;
+        self
+    }
+
+    /// Print the innermost cause first and the error itself last.
+    ///
+    /// This applies to the single-line, multi-line, tree, and HTML layouts. In the multi-line
+    /// layout, the innermost cause replaces the error on the first line, so a different
+    /// [header](Report::caused_by_label) might be appropriate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .reverse(true);
+    /// assert_eq!(report.to_string(), "permission denied: could not load config");
+    /// let report = report.pretty(true).caused_by_label("Context:");
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "permission denied\n\nContext:\n      could not load config",
+    /// );
+    /// ```
+    pub fn reverse(mut self, reverse: bool) -> Self {
+        self.options.reverse = reverse;
+        self
+    }
+
+    /// Print only the innermost cause.
+    ///
+    /// This is useful where space is limited and the innermost cause is the most actionable
+    /// part of the report. If the error has no causes, the error itself is printed. This takes
+    /// precedence over [`Report::reverse`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .root_only(true);
+    /// assert_eq!(report.to_string(), "permission denied");
+    /// ```
+    pub fn root_only(mut self, root_only: bool) -> Self {
+        self.options.root_only = root_only;
+        self
+    }
+
+    /// Print at most `max` causes.
+    ///
+    /// If there are more causes, the remaining causes are replaced by a line such as
+    /// `... and 12 more`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let causes: Vec<Box<dyn std::error::Error + Send + Sync>> = vec![
+    ///     Box::new(std::io::Error::other("could not open file")),
+    ///     Box::new(std::io::Error::other("permission denied")),
+    ///     Box::new(std::io::Error::other("file is locked")),
+    /// ];
+    /// let report = Report::new(error).with_causes(causes).max_causes(1);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "could not load config: could not open file: ... and 2 more",
+    /// );
+    /// ```
+    pub fn max_causes(mut self, max: usize) -> Self {
+        self.options.max_causes = Some(max);
+        self
+    }
+
+    /// Omit causes whose message is already part of the message of the preceding error.
+    ///
+    /// Some errors include the message of their source in their own message. This option avoids
+    /// printing such messages twice.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config: permission denied");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .suppress_embedded(true);
+    /// assert_eq!(report.to_string(), "could not load config: permission denied");
+    /// ```
+    pub fn suppress_embedded(mut self, suppress: bool) -> Self {
+        self.options.suppress_embedded = suppress;
+        self
+    }
+
+    /// Escape control characters in messages.
+    ///
+    /// Messages often contain untrusted input. Control characters, including the escape
+    /// character that starts ANSI escape sequences, are printed as escape sequences such as
+    /// `\u{1b}` instead. Line breaks are not escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("invalid user name \x1b[2J");
+    /// let report = Report::new(error).sanitize(true);
+    /// assert_eq!(report.to_string(), "invalid user name \\u{1b}[2J");
+    /// ```
+    pub fn sanitize(mut self, sanitize: bool) -> Self {
+        self.options.sanitize = sanitize;
+        self
+    }
+
+    /// Limit the length of the message of each cause to `max` characters.
+    ///
+    /// Longer messages are truncated and followed by `…`. The message of the error itself is
+    /// not truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("query failed");
+    /// let cause = std::io::Error::other("syntax error in SELECT * FROM users WHERE");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .max_cause_len(12);
+    /// assert_eq!(report.to_string(), "query failed: syntax error…");
+    /// ```
+    pub fn max_cause_len(mut self, max: usize) -> Self {
+        self.options.max_cause_len = Some(max);
+        self
+    }
+
+    /// Limit the length of the formatted report to `max` bytes.
+    ///
+    /// If the report is longer, it is truncated at a character boundary and `… (truncated)` is
+    /// appended such that the output is at most `max` bytes long. Formatting stops once the limit
+    /// is reached.
+    ///
+    /// Escape sequences and line breaks are never cut, and styles and hyperlinks that are active
+    /// at the cut are closed. If the marker does not fit, the report is truncated without it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .max_len(32);
+    /// assert_eq!(report.to_string(), "could not load co… (truncated)");
+    /// ```
+    pub fn max_len(mut self, max: usize) -> Self {
+        self.options.max_len = Some(max);
+        self
+    }
+
+    /// Merge consecutive messages that are identical.
+    ///
+    /// The merged message is annotated with the number of times it occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("timeout");
+    /// let causes: Vec<Box<dyn std::error::Error + Send + Sync>> = vec![
+    ///     Box::new(std::io::Error::other("timeout")),
+    ///     Box::new(std::io::Error::other("timeout")),
+    ///     Box::new(std::io::Error::other("connection reset")),
+    /// ];
+    /// let report = Report::new(error)
+    ///     .with_causes(causes)
+    ///     .collapse_duplicates(true);
+    /// assert_eq!(report.to_string(), "timeout (x3): connection reset");
+    /// ```
+    pub fn collapse_duplicates(mut self, collapse: bool) -> Self {
+        self.options.collapse_duplicates = collapse;
+        self
+    }
+
+    /// Set the HTML tag that wraps the error when printing an HTML list.
+    ///
+    /// The default is `p`. The tag must consist of ASCII letters and digits and start with a
+    /// letter. Otherwise, `p` is used.
+    pub fn html_root_tag(mut self, tag: &str) -> Self {
+        self.options.html_root_tag = tag.to_string();
+        self
+    }
+
+    /// Use a custom strategy to collect the causes of the error.
+    ///
+    /// The default is [`DefaultStrategy`], which follows [`Error::source`]. Causes added with
+    /// [`Report::with_causes`] are printed after the causes returned by the strategy. Cycles are
+    /// only detected by the default strategy.
+    ///
+    /// See [`CauseStrategy`] for an example.
+    pub fn with_strategy(mut self, strategy: impl CauseStrategy + 'static) -> Self
+    where
+        E: Error + 'static,
+    {
+        self.strategy = Some(Strategy {
+            strategy: Box::new(strategy),
+            upcast: |error| error,
+        });
+        self.source_count = OnceLock::new();
+        self
+    }
+}
+
+impl<E, F> Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    /// Returns a view of the report that it is formatted through.
+    fn borrowed(&self) -> ReportRef<'_, E, F> {
+        ReportRef {
+            error: &self.error,
+            options: &self.options,
+            extra_causes: &self.extra_causes,
+            replacements: &self.replacements,
+            strategy: self.strategy.as_ref(),
+            source_count: &self.source_count,
+            format: PhantomData,
+        }
+    }
+
+    /// Replace the messages of causes.
+    ///
+    /// `f` is called with the index and the value of each cause. If it returns `Some`, the
+    /// returned string is printed instead of the message of the cause. This can be used to redact
+    /// sensitive information.
+    ///
+    /// `f` is only applied to the causes that are part of the report when this function is called.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not connect");
+    /// let cause = std::io::Error::other("invalid password hunter2");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .map_sources(Box::new(|_, cause| {
+    ///         let message = cause.to_string();
+    ///         message
+    ///             .contains("password")
+    ///             .then(|| "invalid password <redacted>".to_string())
+    ///     }));
+    /// assert_eq!(report.to_string(), "could not connect: invalid password <redacted>");
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn map_sources(mut self, f: Box<dyn Fn(usize, &dyn Error) -> Option<String>>) -> Self {
+        let replacements: Vec<_> = self
+            .borrowed()
+            .sources()
+            .enumerate()
+            .filter_map(|(index, error)| f(index, error).map(|message| (index, message)))
+            .collect();
+        self.replacements.extend(replacements);
+        self
+    }
+
+    /// Returns the number of causes of the error.
+    ///
+    /// This includes the causes added with [`Report::with_causes`] but not the annotation of a
+    /// cycle. The chain is only walked on the first call. The result is cached afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(report.source_count(), 1);
+    /// ```
+    pub fn source_count(&self) -> usize {
+        self.borrowed().source_count()
+    }
+
+    /// Returns the message of the error without its causes.
+    ///
+    /// This is equivalent to `report.inner().to_string()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(report.summary(), "could not load config");
+    /// assert_eq!(report.full_chain(), "could not load config: permission denied");
+    /// ```
+    pub fn summary(&self) -> String {
+        self.error.to_string()
+    }
+
+    /// Returns the formatted report.
+    ///
+    /// This is equivalent to `report.to_string()`.
+    pub fn full_chain(&self) -> String {
+        self.to_string()
+    }
+
+    /// Collect the messages of the error and its causes into an [`ErrorDescription`].
+    ///
+    /// The messages of the causes are formatted with the options of this report. Of the options
+    /// that affect the layout, only [`pretty`](Self::pretty) is preserved in the description.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    ///
+    /// let description = report.describe();
+    /// assert_eq!(description.root_message, "could not load config");
+    /// assert_eq!(description.causes, ["permission denied"]);
+    /// ```
+    pub fn describe(&self) -> ErrorDescription {
+        ErrorDescription {
+            root_message: self.borrowed().root().to_string(),
+            causes: self
+                .borrowed()
+                .causes()
+                .map(|cause| cause.to_string())
+                .collect(),
+            pretty: self.options.pretty,
+        }
+    }
+
+    /// Print the report to stderr and exit the process with the given exit code.
+    ///
+    /// Stderr is flushed before exiting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// Report::new(error).pretty(true).print_and_exit(2);
+    /// ```
+    pub fn print_and_exit(self, code: i32) -> ! {
+        eprintln!("{self}");
+        let _ = io::Write::flush(&mut io::stderr());
+        std::process::exit(code)
+    }
+
+    /// Print the report to stderr and exit the process with exit code 1.
+    ///
+    /// This is a shorthand for [`Report::print_and_exit`].
+    pub fn eprint_and_exit(self) -> ! {
+        self.print_and_exit(1)
+    }
+
+    /// Returns the kind of the first `std::io::Error` in the chain.
+    ///
+    /// The error itself is checked first, followed by its causes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    /// use std::io::ErrorKind;
+    ///
+    /// let error = std::io::Error::from(ErrorKind::NotFound);
+    /// assert_eq!(Report::new(error).error_kind(), Some(ErrorKind::NotFound));
+    /// ```
+    pub fn error_kind(&self) -> Option<io::ErrorKind>
+    where
+        E: 'static,
+    {
+        let root: &(dyn Error + 'static) = &self.error;
+        std::iter::once(root)
+            .chain(self.borrowed().sources())
+            .find_map(|error| error.downcast_ref::<io::Error>())
+            .map(io::Error::kind)
+    }
+
+    /// Returns an iterator over the error and its causes together with their depth.
+    ///
+    /// The error has depth 0, its first cause has depth 1, and so on. Causes added with
+    /// [`Report::with_causes`] are included. The chain is walked lazily.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// for (depth, error) in report.depth_first_iter() {
+    ///     println!("{:indent$}{error}", "", indent = 2 * depth);
+    /// }
+    /// ```
+    pub fn depth_first_iter(&self) -> impl Iterator<Item = (usize, &dyn Error)> {
+        let root: &dyn Error = &self.error;
+        std::iter::once(root)
+            .chain(self.borrowed().sources().map(|e| e as &dyn Error))
+            .enumerate()
+    }
+
+    /// Returns an iterator over the lines of the formatted report.
+    ///
+    /// This is useful to prefix each line, for example with a timestamp, before emitting it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error)
+    ///     .with_causes(vec![Box::new(cause)])
+    ///     .pretty(true);
+    /// for line in report.as_lines() {
+    ///     eprintln!("[12:00:00] {line}");
+    /// }
+    /// ```
+    pub fn as_lines(&self) -> LinesReport<'_, E, F> {
+        LinesReport::new(self)
+    }
+
+    /// Format the causes with a closure.
+    ///
+    /// The closure is called with the index of each cause, the cause, and the formatter. It
+    /// can add prefixes, skip causes by writing nothing, or print additional data. Unlike the
+    /// other layouts, the causes are not affected by the formatting options of the report.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("load failed");
+    /// let causes = vec![
+    ///     Box::new(std::io::Error::other("open failed")) as _,
+    ///     Box::new(std::io::Error::from(std::io::ErrorKind::PermissionDenied)) as _,
+    /// ];
+    /// let report = Report::new(error).with_causes(causes);
+    /// let frames = report.format_frames(|index, error, f| {
+    ///     write!(f, "\n  #{index} {error}")?;
+    ///     if let Some(error) = error.downcast_ref::<std::io::Error>() {
+    ///         write!(f, " [{:?}]", error.kind())?;
+    ///     }
+    ///     Ok(())
+    /// });
+    /// assert_eq!(
+    ///     frames.to_string(),
+    ///     "load failed\n  #0 open failed [Other]\n  #1 permission denied [PermissionDenied]",
+    /// );
+    /// ```
+    pub fn format_frames<C>(&self, format: C) -> FramesReport<'_, E, C, F>
+    where
+        C: Fn(usize, &(dyn Error + 'static), &mut fmt::Formatter<'_>) -> fmt::Result,
+    {
+        FramesReport::new(self, format)
+    }
+
+    /// Returns the "Caused by:" section of the pretty-printed report.
+    ///
+    /// This is useful to display the error and its causes in different places, for example in
+    /// the title and the body of a dialog.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(report.summary(), "could not load config");
+    /// assert_eq!(
+    ///     report.cause_section().to_string(),
+    ///     "Caused by:\n      permission denied",
+    /// );
+    /// ```
+    pub fn cause_section(&self) -> CauseSection<'_, E, F> {
+        CauseSection::new(self)
+    }
+
+    /// Format the report as a single line.
+    ///
+    /// This ignores whether pretty-printing is enabled. It can be used to implement `Display`
+    /// for types that contain errors.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    /// use std::fmt;
+    ///
+    /// struct Failure {
+    ///     job: u32,
+    ///     error: std::io::Error,
+    /// }
+    ///
+    /// impl fmt::Display for Failure {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "job {} failed: ", self.job)?;
+    ///         Report::new(&self.error).fmt_singleline(f)
+    ///     }
+    /// }
+    /// ```
+    pub fn fmt_singleline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.borrowed().fmt_singleline(f)
+    }
+
+    /// Format the report as multiple lines, with each error cause on its own line.
+    ///
+    /// This ignores whether pretty-printing is enabled and always uses the numbered layout
+    /// rather than the tree or HTML layouts.
+    pub fn fmt_multiline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.borrowed().fmt_multiline(f)
+    }
+}
+
+/// A borrowed view of a [`Report`] that the report is formatted through.
+///
+/// [`format_singleline`] and [`format_multiline`] create a view with borrowed options, so
+/// formatting does not require cloning the options into a report.
+struct ReportRef<'a, E, F> {
+    error: &'a E,
+    options: &'a FormatOptions,
+    extra_causes: &'a [Box<dyn Error + Send + Sync + 'static>],
+    replacements: &'a HashMap<usize, String>,
+    strategy: Option<&'a Strategy<E>>,
+    source_count: &'a OnceLock<usize>,
+    format: PhantomData<&'a F>,
+}
+
+impl<E, F> Clone for ReportRef<'_, E, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E, F> Copy for ReportRef<'_, E, F> {}
+
+impl<'a, E, F> ReportRef<'a, E, F> {
+    /// Returns the header printed before the causes in pretty mode.
+    fn header_label(self) -> Cow<'a, str> {
+        #[cfg(feature = "i18n")]
+        if let Some(labels) = &self.options.labels {
+            return labels.caused_by();
+        }
+        Cow::Borrowed(&self.options.caused_by_label)
+    }
+
+    /// Format the message that replaces `count` causes that are not printed.
+    fn fmt_elided(self, f: &mut fmt::Formatter<'_>, count: usize) -> fmt::Result {
+        #[cfg(feature = "i18n")]
+        if let Some(labels) = &self.options.labels {
+            return f.write_str(&labels.elided(count));
+        }
+        write!(f, "... and {count} more")
+    }
+}
+
+impl<'a, E, F> ReportRef<'a, E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    /// Returns an iterator over the causes of the error, excluding the error itself.
+    fn sources(self) -> impl Iterator<Item = &'a (dyn Error + 'static)> + Clone {
+        let extra = self
+            .extra_causes
+            .iter()
+            .map(|e| &**e as &(dyn Error + 'static));
+        let custom: Option<Vec<_>> = self
+            .strategy
+            .as_ref()
+            .map(|s| s.strategy.causes((s.upcast)(self.error)).collect());
+        let natural = match custom {
+            Some(_) => None,
+            None => self.error.source(),
+        };
+        let root = self.error as *const E as *const ();
+        sources_after(root, natural)
+            .chain(custom.into_iter().flatten())
+            .chain(extra)
+    }
+
+    /// Returns the number of causes of the error.
+    fn source_count(self) -> usize {
+        *self.source_count.get_or_init(|| self.sources().count())
+    }
+
+    /// Returns the bullet that precedes causes in a bullet list.
+    fn bullet_char(self) -> char {
+        match self.options.box_style {
+            BoxStyle::Ascii if !self.options.bullet.is_ascii() => '-',
+            _ => self.options.bullet,
+        }
+    }
+
+    /// Returns a type that displays the error according to the configuration of the report.
+    fn root(self) -> Root<'a, E, F> {
+        Root {
+            report: self,
+            repeat: 1,
+        }
+    }
+
+    /// Returns an iterator over the causes of the error that display them according to the
+    /// configuration of the report.
+    fn causes(self) -> impl Iterator<Item = Cause<'a, E, F>> + Clone {
+        let causes = self.sources().enumerate().map(move |(index, error)| Cause {
+            report: self,
+            index,
+            kind: CauseKind::Error(error),
+            repeat: 1,
+        });
+        let natural = match self.strategy {
+            Some(_) => None,
+            None => self.error.source(),
+        };
+        let root = self.error as *const E as *const ();
+        let cycle = match natural {
+            Some(source) if has_cycle(root, natural) => match &self.options.on_cycle {
+                CycleBehavior::Annotate(_) => Some(Cause {
+                    report: self,
+                    index: self.source_count(),
+                    kind: CauseKind::Cycle,
+                    repeat: 1,
+                }),
+                CycleBehavior::Stop => None,
+                CycleBehavior::Panic => {
+                    let mut chain = self.error.to_string();
+                    for error in sources_after(root, Some(source)) {
+                        let _ = write!(chain, ": {error}");
+                    }
+                    panic!("cycle detected in error chain: {chain}");
+                }
+            },
+            _ => None,
+        };
+        causes.chain(cycle)
+    }
+
+    /// Returns the first message and an iterator over the remaining messages in the order in
+    /// which they should be printed.
+    ///
+    /// This is the error followed by its causes unless the order is reversed or only the
+    /// innermost cause is printed. At most `depth` causes are returned in addition to the
+    /// [maximum number of causes](Report::max_causes).
+    fn entries(
+        self,
+        depth: Option<usize>,
+    ) -> (
+        Entry<'a, E, F>,
+        impl Iterator<Item = Entry<'a, E, F>> + Clone,
+    ) {
+        let options = &self.options;
+        let reorder = options.reverse
+            || options.root_only
+            || options.collapse_duplicates
+            || options.suppress_embedded;
+        let reordered = reorder.then(|| {
+            let mut entries: Vec<_> = std::iter::once(Entry::Root(self.root()))
+                .chain(self.causes().map(Entry::Cause))
+                .collect();
+            if options.suppress_embedded {
+                entries = suppress_embedded(entries);
+            }
+            if options.collapse_duplicates {
+                entries = collapse_duplicates(entries);
+            }
+            // The annotation of a cycle remains at the end.
+            let errors = match entries.last() {
+                Some(entry) if entry.is_cycle() => entries.len() - 1,
+                _ => entries.len(),
+            };
+            if options.root_only {
+                entries.swap(0, errors - 1);
+                entries.truncate(1);
+            } else if options.reverse {
+                entries[..errors].reverse();
+            }
+            entries
+        });
+        let head = match &reordered {
+            Some(entries) => entries[0],
+            None => Entry::Root(self.root()),
+        };
+        let natural = match reordered {
+            Some(_) => None,
+            None => Some(self.causes().map(Entry::Cause)),
+        };
+        let rest = natural
+            .into_iter()
+            .flatten()
+            .chain(reordered.into_iter().flatten().skip(1));
+        let limit = self
+            .options
+            .max_causes
+            .unwrap_or(usize::MAX)
+            .min(depth.unwrap_or(usize::MAX));
+        let elided = rest.clone().skip(limit).filter(|e| !e.is_cycle()).count();
+        let rest = rest
+            .take(limit)
+            .chain((elided > 0).then_some(Entry::Elided(self, elided)));
+        (head, rest)
+    }
+
+    /// Returns the styles to use, which are all empty if colors are disabled.
+    fn styles(self) -> ThemeRef<'a> {
+        let theme = &self.options.theme;
+        if self.options.colored {
+            ThemeRef {
+                header: &theme.header,
+                index: &theme.index,
+                message: &theme.message,
+                root_cause: &theme.root_cause,
+            }
+        } else {
+            ThemeRef::default()
+        }
+    }
+
+    /// Writes the [program name](Report::program_name) and the [title prefix](Report::title_prefix).
+    ///
+    /// Nothing is written in JSON output. The title prefix is also omitted in layouts that have
+    /// their own header.
+    fn write_title_prefix(self, out: &mut dyn Write) -> fmt::Result {
+        let options = &self.options;
+        if F::JSON || options.json {
+            return Ok(());
+        }
+        if let Some(name) = &options.program_name {
+            write!(out, "{name}: ")?;
+        }
+        match &options.title_prefix {
+            Some(_) if options.cargo_style || options.rustc_style => Ok(()),
+            Some(title) => write!(out, "{}: ", Styled::new(title, self.styles().header)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the style of the message of a cause in pretty mode.
+    ///
+    /// The deepest cause uses the root cause style if it is set.
+    fn message_style<'t>(self, entry: &Entry<'_, E, F>, theme: ThemeRef<'t>) -> &'t str {
+        let is_root_cause = match entry {
+            Entry::Cause(cause) => {
+                matches!(cause.kind, CauseKind::Error(_)) && cause.index + 1 == self.source_count()
+            }
+            _ => false,
+        };
+        if is_root_cause && !theme.root_cause.is_empty() {
+            theme.root_cause
+        } else {
+            theme.message
+        }
+    }
+
+    /// Format the report as a single line.
+    fn fmt_singleline(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.styles();
+        let (head, rest) = self.entries(f.precision());
+
+        let mut f = Flattened {
+            inner: f,
+            replacement: self.options.newline_replacement.as_deref(),
+        };
+
+        write!(f, "{}", Styled::new(head, theme.message))?;
+
+        let mut rest = rest.peekable();
+        let parenthesized = self.options.parenthesized && rest.peek().is_some();
+        for (i, cause) in rest.enumerate() {
+            if !parenthesized {
+                f.write_str(&self.options.separator)?;
+            } else if i == 0 {
+                f.write_str(" (caused by: ")?;
+            } else {
+                f.write_str("; ")?;
+            }
+            if !matches!(cause, Entry::Elided(..)) {
+                f.write_str(&self.options.cause_prefix)?;
+            }
+            if let Some(name) = cause.type_name() {
+                write!(f, "<{name}>")?;
+            }
+            write!(f, "{}", Styled::new(cause, theme.message))?;
+        }
+        if parenthesized {
+            f.write_str(")")?;
+        }
+
+        Ok(())
+    }
+
+    /// Format the report as multiple lines, with each error cause on its own line.
+    fn fmt_multiline(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wrap = f.width().or(self.options.wrap_width);
+
+        let (head, mut rest) = self.entries(f.precision());
+        let head = Wrapped::new(head, wrap);
+        let head = Styled::new(head, self.styles().message);
+        match &self.options.header_template {
+            Some(template) => write_template(f, template, &"", &head)?,
+            None => write!(f, "{head}")?,
+        }
+
+        if rest.next().is_some() {
+            if self.options.blank_line_before_causes {
+                write!(f, "\n\n")?;
+            } else {
+                writeln!(f)?;
+            }
+            self.fmt_causes(f, wrap)?;
+        }
+
+        self.fmt_footer(f)
+    }
+
+    /// Format the "Caused by:" section of the multi-line format.
+    ///
+    /// Nothing is written if there are no causes.
+    fn fmt_causes(self, f: &mut fmt::Formatter<'_>, wrap: Option<usize>) -> fmt::Result {
+        let mut causes = self.entries(f.precision()).1.peekable();
+
+        if causes.peek().is_some() {
+            let theme = self.styles();
+            let label = self.header_label();
+            write!(f, "{}", Styled::new(label, theme.header))?;
+
+            if let Some(template) = &self.options.cause_template {
+                let first = self.options.first_number;
+                for (ind, error) in causes.enumerate() {
+                    let number = first.saturating_add(ind);
+                    let index: &dyn fmt::Display = match error {
+                        Entry::Elided(..) => &"",
+                        _ => &number,
+                    };
+                    writeln!(f)?;
+                    let style = self.message_style(&error, theme);
+                    write_template(f, template, index, &Styled::new(error, style))?;
+                }
+                return Ok(());
+            }
+
+            let multiple = match self.options.cause_numbering {
+                CauseNumbering::Auto => causes.clone().nth(1).is_some(),
+                CauseNumbering::Always => true,
+                CauseNumbering::Never => false,
+            };
+
+            let first = self.options.first_number;
+            let last = first.saturating_add(causes.clone().count() - 1);
+            let width = self.options.min_number_digits.max(last.to_string().len());
+            let separator = &self.options.number_separator;
+            let indent = match &self.options.indentation {
+                Some(indentation) => indentation.to_string(),
+                None => " ".repeat(width + width_of(separator)),
+            };
+
+            let bullet = self.options.use_bullet_list.then(|| self.bullet_char());
+
+            for (ind, error) in causes.enumerate() {
+                let ind = first.saturating_add(ind);
+                let multiple = multiple && !matches!(error, Entry::Elided(..));
+                let prefix = match (bullet, multiple, error.type_name()) {
+                    (Some(bullet), _, Some(name)) => format!("  {bullet} ({name}) "),
+                    (Some(bullet), _, None) => format!("  {bullet} "),
+                    (None, true, Some(name)) => format!("{ind: >width$} ({name}){separator}"),
+                    (None, true, None) => format!("{ind: >width$}{separator}"),
+                    (None, false, Some(name)) => format!("{indent}({name}) "),
+                    (None, false, None) => indent.clone(),
+                };
+                let indent = match (bullet, multiple) {
+                    _ if self.options.indentation.is_some() => indent.clone(),
+                    (Some(bullet), _) => " ".repeat(width_of(&format!("  {bullet} "))),
+                    (None, true) => " ".repeat(width_of(&prefix)),
+                    (None, false) => indent.clone(),
+                };
+                let used = width_of(&prefix).max(width_of(&indent));
+                let message_style = self.message_style(&error, theme);
+                let error = Wrapped::new(error, wrap.map(|w| w.saturating_sub(used)));
+                let style = if prefix.trim().is_empty() {
+                    ""
+                } else {
+                    theme.index
+                };
+                let prefix = Styled::new(prefix, style);
+                let error = Styled::new(error, message_style);
+                writeln!(f)?;
+                let mut indented = Indented {
+                    inner: f,
+                    indent: &indent,
+                };
+                write!(indented, "{prefix}{error}")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Format the report as HTML, with the error causes in an ordered list.
+    fn fmt_html_list(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tag = &self.options.html_root_tag;
+        // Anything but a plain element name could inject markup.
+        let valid = tag.starts_with(|c: char| c.is_ascii_alphabetic())
+            && tag.chars().all(|c| c.is_ascii_alphanumeric());
+        let tag = if valid { tag } else { "p" };
+
+        let (head, rest) = self.entries(f.precision());
+
+        write!(f, "<{tag}>")?;
+        write!(HtmlEscaped { inner: f }, "{head}")?;
+        write!(f, "</{tag}>")?;
+
+        let mut causes = rest.peekable();
+
+        if causes.peek().is_some() {
+            f.write_str("<ol>")?;
+            for error in causes {
+                f.write_str("<li>")?;
+                write!(HtmlEscaped { inner: f }, "{error}")?;
+                f.write_str("</li>")?;
+            }
+            f.write_str("</ol>")?;
+        }
+
+        Ok(())
+    }
+
+    /// Format the report as Markdown for chat messages.
+    fn fmt_markdown(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (head, _) = self.entries(f.precision());
+
+        f.write_str("**")?;
+        write!(MarkdownEscaped { inner: f }, "{head}")?;
+        f.write_str("**")?;
+
+        let causes = match f.precision() {
+            Some(depth) => format!("{:.depth$}", CauseList(self)),
+            None => CauseList(self).to_string(),
+        };
+        if !causes.is_empty() {
+            // The fence must be longer than any run of backticks in the causes.
+            let longest = causes.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+            let fence = "`".repeat(longest.max(2) + 1);
+            write!(f, "\n{fence}\n{causes}\n{fence}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Format the report as a tree, with each error cause on its own branch.
+    fn fmt_tree(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wrap = f.width().or(self.options.wrap_width);
+
+        let theme = self.styles();
+        let (head, rest) = self.entries(f.precision());
+        let head = Wrapped::new(head, wrap);
+        write!(f, "{}", Styled::new(head, theme.message))?;
+
+        let mut causes = rest.peekable();
+
+        while let Some(error) = causes.next() {
+            let last = causes.peek().is_none();
+            let (branch, continuation) = if last {
+                (self.options.box_style.corner(), "    ")
+            } else {
+                (
+                    self.options.box_style.tee(),
+                    self.options.box_style.continuation(),
+                )
+            };
+            let style = self.message_style(&error, theme);
+            let error = Wrapped::new(error, wrap.map(|w| w.saturating_sub(4)));
+            let error = Styled::new(error, style);
+            writeln!(f)?;
+            f.write_str(branch)?;
+            let mut indented = Indented {
+                inner: f,
+                indent: continuation,
+            };
+            write!(indented, "{error}")?;
+        }
+
+        self.fmt_footer(f)
+    }
+
+    /// Format the report as a JSON object.
+    fn fmt_json(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{\"message\":{},\"causes\":[",
+            JsonString(&self.root().to_string()),
+        )?;
+        for (i, cause) in self.causes().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", JsonString(&cause.to_string()))?;
+        }
+        f.write_str("]}")
+    }
+
+    /// Format the report with each cause on its own line behind the header.
+    fn fmt_plain(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.styles();
+        let (head, rest) = self.entries(f.precision());
+        write!(f, "{}", Styled::new(head, theme.message))?;
+
+        let label = self.header_label();
+        for error in rest {
+            writeln!(f)?;
+            write!(f, "{} ", Styled::new(&*label, theme.header))?;
+            let style = self.message_style(&error, theme);
+            write!(f, "{}", Styled::new(error, style))?;
+        }
+
+        self.fmt_footer(f)
+    }
+
+    /// Format the report like the errors printed by Cargo.
+    fn fmt_cargo(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.styles();
+        let (head, rest) = self.entries(f.precision());
+        write!(f, "{}: ", Styled::new("error", theme.header))?;
+        write!(f, "{}", Styled::new(head, theme.message))?;
+
+        let label = self.header_label();
+        for error in rest {
+            write!(f, "\n\n{}\n  ", Styled::new(&*label, theme.header))?;
+            let style = self.message_style(&error, theme);
+            let mut indented = Indented {
+                inner: f,
+                indent: "  ",
+            };
+            write!(indented, "{}", Styled::new(error, style))?;
+        }
+
+        Ok(())
+    }
+
+    /// Format the report like the diagnostics printed by rustc.
+    fn fmt_rustc(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let theme = self.styles();
+        let (head, rest) = self.entries(f.precision());
+        match &self.options.error_code {
+            Some(code) => write!(
+                f,
+                "{}: ",
+                Styled::new(format_args!("error[{code}]"), theme.header)
+            )?,
+            None => write!(f, "{}: ", Styled::new("error", theme.header))?,
+        }
+        write!(f, "{}", Styled::new(head, theme.message))?;
+
+        for error in rest {
+            write!(f, "\n  {} ", Styled::new("= note:", theme.index))?;
+            let style = self.message_style(&error, theme);
+            let mut indented = Indented {
+                inner: f,
+                indent: "          ",
+            };
+            write!(indented, "{}", Styled::new(error, style))?;
+        }
+
+        Ok(())
+    }
+
+    /// Format the report with each cause indented below its parent.
+    fn fmt_nested(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wrap = f.width().or(self.options.wrap_width);
+
+        let theme = self.styles();
+        let (head, rest) = self.entries(f.precision());
+        let head = Wrapped::new(head, wrap);
+        write!(f, "{}", Styled::new(head, theme.message))?;
+
+        for (depth, error) in rest.enumerate() {
+            let parent = " ".repeat(4 * depth);
+            let indent = " ".repeat(4 * (depth + 1));
+            let style = self.message_style(&error, theme);
+            let error = Wrapped::new(error, wrap.map(|w| w.saturating_sub(indent.len())));
+            let error = Styled::new(error, style);
+            writeln!(f)?;
+            f.write_str(&parent)?;
+            f.write_str(self.options.box_style.corner())?;
+            let mut indented = Indented {
+                inner: f,
+                indent: &indent,
+            };
+            write!(indented, "{error}")?;
+        }
+
+        self.fmt_footer(f)
+    }
+
+    /// Format the [footer](Report::footer) of a pretty report.
+    fn fmt_footer(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.options.footer {
+            Some(footer) => write!(f, "\n\n{footer}"),
+            None => Ok(()),
+        }
+    }
+
+    /// Format the report according to the runtime options.
+    fn fmt_dynamic(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.options.panel {
+            Some(title) => self.fmt_panel(f, title),
+            None => self.fmt_layout(f),
+        }
+    }
+
+    /// Format the report inside a panel with the given title.
+    fn fmt_panel(self, f: &mut fmt::Formatter<'_>, title: &str) -> fmt::Result {
+        let wrap = f.width().or(self.options.wrap_width);
+        let mut content = String::new();
+        let layout = Layout(self);
+        write_with_flags(&mut content, f, wrap.map(|w| w.saturating_sub(4)), &layout)?;
+
+        let chars = self.options.box_style.panel();
+        let lines: Vec<_> = content.split('\n').collect();
+        // The title must fit into the top border.
+        let title = title.replace(['\r', '\n'], " ");
+        let title_width = if title.is_empty() {
+            0
+        } else {
+            visible_width(&title) + 2
+        };
+        let width = lines
+            .iter()
+            .map(|line| visible_width(line))
+            .max()
+            .unwrap_or_default()
+            .max(title_width);
+        let horizontal = |n: usize| chars.horizontal.repeat(n);
+
+        f.write_str(chars.top_left)?;
+        if title.is_empty() {
+            f.write_str(&horizontal(width + 2))?;
+        } else {
+            write!(f, "{} {title} ", chars.horizontal)?;
+            f.write_str(&horizontal(width + 1 - title_width))?;
+        }
+        f.write_str(chars.top_right)?;
+        for line in lines {
+            let padding = " ".repeat(width - visible_width(line));
+            write!(f, "\n{} {line}{padding} {}", chars.vertical, chars.vertical)?;
+        }
+        write!(f, "\n{}", chars.bottom_left)?;
+        f.write_str(&horizontal(width + 2))?;
+        f.write_str(chars.bottom_right)
+    }
+
+    /// Format the report according to the layout options.
+    fn fmt_layout(self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pretty = self.options.pretty || f.alternate();
+        if self.options.json {
+            self.fmt_json(f)
+        } else if self.options.cargo_style {
+            self.fmt_cargo(f)
+        } else if self.options.rustc_style {
+            self.fmt_rustc(f)
+        } else if self.options.markdown {
+            self.fmt_markdown(f)
+        } else if pretty && self.options.html_list {
+            self.fmt_html_list(f)
+        } else if pretty && self.options.tree {
+            self.fmt_tree(f)
+        } else if pretty && self.options.nested {
+            self.fmt_nested(f)
+        } else if pretty && self.options.plain {
+            self.fmt_plain(f)
+        } else if pretty {
+            self.fmt_multiline(f)
+        } else if f.width().is_some() {
+            let line = match f.precision() {
+                Some(depth) => format!("{:.depth$}", AsSingleLine(self)),
+                None => AsSingleLine(self).to_string(),
+            };
+            pad(f, &line)
+        } else {
+            self.fmt_singleline(f)
+        }
+    }
+}
+
+impl<E> From<E> for Report<E>
+where
+    E: Error,
+{
+    fn from(error: E) -> Self {
+        Report {
+            error,
+            options: style::default_options(),
+            extra_causes: Vec::new(),
+            replacements: HashMap::new(),
+            strategy: None,
+            format: PhantomData,
+            source_count: OnceLock::new(),
+        }
+    }
+}
+
+/// Formats the report.
+///
+/// If a precision is specified, e.g. `{:.3}`, at most that many causes are printed, as if
+/// [`Report::max_causes`] had been set.
+///
+/// The alternate flag, e.g. `{:#}`, pretty-prints a report with the [`Dynamic`] format even if
+/// [`Report::pretty`] was not enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::Report;
+///
+/// let error = std::io::Error::other("could not load config");
+/// let cause = std::io::Error::other("permission denied");
+/// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+/// assert_eq!(format!("{report:.0}"), "could not load config: ... and 1 more");
+/// assert_eq!(
+///     format!("{report:#}"),
+///     "could not load config\n\nCaused by:\n      permission denied",
+/// );
+/// ```
+impl<E, F> fmt::Display for Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(res) = hook::fmt_with_hook(&self.error, f) {
+            return res;
+        }
+        let prefix = self.options.line_prefix.as_deref();
+        if prefix.is_none() && self.options.max_len.is_none() && !self.options.crlf {
+            self.borrowed().write_title_prefix(f)?;
+            return F::fmt(self, f);
+        }
+        let max = self.options.max_len.unwrap_or(usize::MAX);
+        let mut limited = Limit {
+            out: String::new(),
+            bytes: max,
+            truncated: false,
+        };
+        let mut crlf = Crlf {
+            inner: &mut limited,
+            enabled: self.options.crlf,
+            after_cr: false,
+        };
+        let prefix = prefix.unwrap_or_default();
+        let mut out = Indented {
+            inner: &mut crlf,
+            indent: prefix,
+        };
+        let unlimited = Unlimited(self);
+        // Errors indicate that the limit was reached.
+        if out.write_str(prefix).is_ok() && self.borrowed().write_title_prefix(&mut out).is_ok() {
+            let _ = write_with_flags(&mut out, f, f.width(), &unlimited);
+        }
+        let out = &mut limited.out;
+        if limited.truncated {
+            truncate_output(out, max);
+        }
+        f.write_str(out)
+    }
+}
+
+/// Formats `value` into `out` with the precision and alternate flag of `f` and the given width.
+fn write_with_flags(
+    out: &mut dyn Write,
+    f: &fmt::Formatter<'_>,
+    width: Option<usize>,
+    value: &dyn fmt::Display,
+) -> fmt::Result {
+    match (width, f.precision(), f.alternate()) {
+        (Some(w), Some(d), true) => write!(out, "{value:#w$.d$}"),
+        (Some(w), Some(d), false) => write!(out, "{value:w$.d$}"),
+        (Some(w), None, true) => write!(out, "{value:#w$}"),
+        (Some(w), None, false) => write!(out, "{value:w$}"),
+        (None, Some(d), true) => write!(out, "{value:#.d$}"),
+        (None, Some(d), false) => write!(out, "{value:.d$}"),
+        (None, None, true) => write!(out, "{value:#}"),
+        (None, None, false) => write!(out, "{value}"),
+    }
+}
+
+/// Wrapper type for formatting a report without a panel.
+struct Layout<'a, E, F>(ReportRef<'a, E, F>);
+
+impl<E, F> fmt::Display for Layout<'_, E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_layout(f)
+    }
+}
+
+/// Wrapper type for formatting only the causes of a report.
+struct CauseList<'a, E, F>(ReportRef<'a, E, F>);
+
+impl<E, F> fmt::Display for CauseList<'_, E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_causes(f, None)
+    }
+}
+
+/// Wrapper type for formatting a report without post-processing the output.
+struct Unlimited<'a, E, F>(&'a Report<E, F>);
+
+impl<E, F> fmt::Display for Unlimited<'_, E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        F::fmt(self.0, f)
+    }
+}
+
+/// Dereferences to the error as a `dyn Error`.
+///
+/// This allows calling the methods of [`Error`] and `dyn Error` on a report. There is no
+/// `Deref<Target = E>` implementation. Use [`Report::inner`] to access the concrete error type.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::Report;
+///
+/// let error = std::io::Error::other("could not load config");
+/// let report = Report::new(error);
+/// assert!(report.source().is_none());
+/// assert!(report.downcast_ref::<std::io::Error>().is_some());
+/// ```
+impl<E, F> Deref for Report<E, F>
+where
+    E: Error + 'static,
+{
+    type Target = dyn Error;
+
+    fn deref(&self) -> &Self::Target {
+        &self.error
+    }
+}
+
+// This type intentionally outputs the same format for `Display` and `Debug`for
+// situations where you unwrap a `Report` or return it from main.
+impl<E, F> fmt::Debug for Report<E, F>
+where
+    Report<E, F>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Returns the kind of the first `std::io::Error` in the source chain of `error`.
+///
+/// `error` itself is checked first, followed by its sources.
+pub fn io_error_kind(error: &(dyn Error + 'static)) -> Option<io::ErrorKind> {
+    source_chain(error)
+        .find_map(|error| error.downcast_ref::<io::Error>())
+        .map(io::Error::kind)
+}
+
+/// Format an error and its sources as a single line.
+///
+/// This is equivalent to [`Report::fmt_singleline`] on a report with the given options.
+pub fn format_singleline<E>(
+    error: &E,
+    options: &FormatOptions,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    E: Error + ?Sized,
+{
+    format_borrowed(&error, options, f, |report, f| report.fmt_singleline(f))
+}
+
+/// Format an error and its sources as multiple lines.
+///
+/// This is equivalent to [`Report::fmt_multiline`] on a report with the given options.
+pub fn format_multiline<E>(
+    error: &E,
+    options: &FormatOptions,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    E: Error + ?Sized,
+{
+    format_borrowed(&error, options, f, |report, f| report.fmt_multiline(f))
 }
 
-impl<E> Report<E>
+/// Format `error` with `fmt` without cloning the options into a report.
+fn format_borrowed<E>(
+    error: &E,
+    options: &FormatOptions,
+    f: &mut fmt::Formatter<'_>,
+    fmt: fn(ReportRef<'_, E, Dynamic>, &mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result
 where
     E: Error,
 {
-    /// Format the report as a single line.
-    fn fmt_singleline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.error)?;
+    let replacements = HashMap::new();
+    let source_count = OnceLock::new();
+    let report = ReportRef {
+        error,
+        options,
+        extra_causes: &[],
+        replacements: &replacements,
+        strategy: None,
+        source_count: &source_count,
+        format: PhantomData,
+    };
+    fmt(report, f)
+}
 
-        let sources = self.error.source().into_iter().flat_map(Source::new);
+/// Write a report to `writer` through a buffer of `buffer_size` bytes.
+///
+/// The buffer is flushed before this function returns. This avoids issuing one system call per
+/// message when writing to unbuffered sinks such as files or sockets.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{write_buffered, Report};
+///
+/// let error = std::io::Error::other("could not load config");
+/// let mut out = Vec::new();
+/// write_buffered(&Report::new(error), &mut out, 8 * 1024).unwrap();
+/// assert_eq!(out, b"could not load config");
+/// ```
+pub fn write_buffered<E, F, W>(
+    report: &Report<E, F>,
+    writer: W,
+    buffer_size: usize,
+) -> io::Result<()>
+where
+    E: Error,
+    F: ReportFormat,
+    W: io::Write,
+{
+    let mut writer = io::BufWriter::with_capacity(buffer_size, writer);
+    io::Write::write_fmt(&mut writer, format_args!("{report}"))?;
+    io::Write::flush(&mut writer)
+}
 
-        for cause in sources {
-            write!(f, ": {cause}")?;
-        }
+/// Wrapper type for displaying a report as a single line.
+pub(crate) struct AsSingleLine<'a, E, F>(ReportRef<'a, E, F>);
 
-        Ok(())
+impl<E, F> fmt::Display for AsSingleLine<'_, E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_singleline(f)
     }
+}
 
-    /// Format the report as multiple lines, with each error cause on its own line.
-    fn fmt_multiline(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let error = &self.error;
+/// Wrapper type for displaying a cause of a report.
+struct Cause<'a, E, F> {
+    report: ReportRef<'a, E, F>,
+    index: usize,
+    kind: CauseKind<'a>,
+    /// The number of consecutive causes with the same message that this cause represents.
+    repeat: usize,
+}
 
-        write!(f, "{error}")?;
+/// The kinds of entries in the cause list of a report.
+#[derive(Copy, Clone)]
+enum CauseKind<'a> {
+    /// A cause of the error.
+    Error(&'a (dyn Error + 'static)),
+    /// The annotation that marks a cycle in the source chain.
+    Cycle,
+}
 
-        if let Some(cause) = error.source() {
-            write!(f, "\n\nCaused by:")?;
+impl<E, F> Clone for Cause<'_, E, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
 
-            let multiple = cause.source().is_some();
+impl<E, F> Copy for Cause<'_, E, F> {}
 
-            for (ind, error) in Source::new(cause).enumerate() {
-                writeln!(f)?;
-                let mut indented = Indented { inner: f };
-                if multiple {
-                    write!(indented, "{ind: >4}: {error}")?;
-                } else {
-                    write!(indented, "      {error}")?;
+impl<E, F> Cause<'_, E, F> {
+    /// Returns the name of the type of the cause if it should be printed.
+    fn type_name(&self) -> Option<&'static str> {
+        if !self.report.options.show_cause_types {
+            return None;
+        }
+        let CauseKind::Error(error) = self.kind else {
+            return None;
+        };
+        let cause_types = &self.report.options.cause_types;
+        cause_types
+            .iter()
+            .chain(BUILTIN_CAUSE_TYPES)
+            .find(|cause_type| (cause_type.is)(error))
+            .map(|cause_type| (cause_type.name)())
+    }
+}
+
+impl<E, F> fmt::Display for Cause<'_, E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut linked = Linked::new(&mut *f, self.report.options.hyperlinks);
+        let mut sanitized = Sanitized {
+            inner: &mut linked,
+            enabled: self.report.options.sanitize,
+        };
+        let mut truncated = Truncated {
+            inner: &mut sanitized,
+            remaining: self.report.options.max_cause_len,
+            truncated: false,
+        };
+        if self.report.options.capitalize_messages {
+            self.write_message(&mut Capitalized::new(&mut truncated))?;
+        } else {
+            self.write_message(&mut truncated)?;
+        }
+        let truncated = truncated.truncated;
+        linked.finish()?;
+        if truncated {
+            f.write_char('…')?;
+        }
+        write_repeat(f, self.repeat)
+    }
+}
+
+impl<E, F> Cause<'_, E, F> {
+    fn write_message(&self, f: &mut dyn Write) -> fmt::Result {
+        let error = match self.kind {
+            CauseKind::Error(error) => error,
+            CauseKind::Cycle => {
+                if let CycleBehavior::Annotate(annotation) = &self.report.options.on_cycle {
+                    f.write_str(annotation)?;
                 }
+                return Ok(());
+            }
+        };
+        match self.report.replacements.get(&self.index) {
+            Some(message) => f.write_str(message)?,
+            None => write!(f, "{}", Hooked::new(error))?,
+        }
+        if self.report.options.show_io_error_kind {
+            if let Some(error) = error.downcast_ref::<std::io::Error>() {
+                write!(f, " ({:?})", error.kind())?;
             }
         }
-
         Ok(())
     }
 }
 
-impl<E> From<E> for Report<E>
+/// A message in the output of a report, which is either the error or one of its causes.
+enum Entry<'a, E, F> {
+    Root(Root<'a, E, F>),
+    Cause(Cause<'a, E, F>),
+    /// The number of causes that are not printed due to the limit on the number of causes.
+    Elided(ReportRef<'a, E, F>, usize),
+}
+
+impl<E, F> Clone for Entry<'_, E, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E, F> Copy for Entry<'_, E, F> {}
+
+impl<E, F> Entry<'_, E, F> {
+    /// Returns the name of the type of the message if it should be printed.
+    fn type_name(&self) -> Option<&'static str> {
+        match self {
+            Entry::Root(_) | Entry::Elided(..) => None,
+            Entry::Cause(cause) => cause.type_name(),
+        }
+    }
+
+    /// Returns whether this is the annotation of a cycle.
+    fn is_cycle(&self) -> bool {
+        matches!(
+            self,
+            Entry::Cause(Cause {
+                kind: CauseKind::Cycle,
+                ..
+            })
+        )
+    }
+}
+
+impl<E, F> Entry<'_, E, F> {
+    /// Returns the number of repetitions that this entry represents.
+    fn repeat_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Entry::Root(root) => Some(&mut root.repeat),
+            Entry::Cause(cause) => Some(&mut cause.repeat),
+            Entry::Elided(..) => None,
+        }
+    }
+}
+
+/// Removes causes whose message is contained in the message of the preceding error.
+fn suppress_embedded<E, F>(entries: Vec<Entry<'_, E, F>>) -> Vec<Entry<'_, E, F>>
 where
     E: Error,
 {
-    fn from(error: E) -> Self {
-        Report {
-            error,
-            pretty: false,
+    let mut kept = Vec::with_capacity(entries.len());
+    let mut parent = String::new();
+    for entry in entries {
+        let message = entry.to_string();
+        let embedded = matches!(entry, Entry::Cause(_))
+            && !entry.is_cycle()
+            && !message.is_empty()
+            && parent.contains(&message);
+        if !embedded {
+            kept.push(entry);
+        }
+        parent = message;
+    }
+    kept
+}
+
+/// Merges consecutive entries with the same message.
+fn collapse_duplicates<E, F>(entries: Vec<Entry<'_, E, F>>) -> Vec<Entry<'_, E, F>>
+where
+    E: Error,
+{
+    let mut collapsed: Vec<Entry<'_, E, F>> = Vec::with_capacity(entries.len());
+    let mut previous = None;
+    for entry in entries {
+        let message = entry.to_string();
+        if previous.as_ref() == Some(&message) {
+            if let Some(repeat) = collapsed.last_mut().and_then(|e| e.repeat_mut()) {
+                *repeat += 1;
+                continue;
+            }
         }
+        previous = Some(message);
+        collapsed.push(entry);
     }
+    collapsed
 }
 
-impl<E> fmt::Display for Report<E>
+impl<E, F> fmt::Display for Entry<'_, E, F>
 where
     E: Error,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.pretty {
-            self.fmt_multiline(f)
+        match self {
+            Entry::Root(root) => root.fmt(f),
+            Entry::Cause(cause) => cause.fmt(f),
+            Entry::Elided(report, n) => report.fmt_elided(f, *n),
+        }
+    }
+}
+
+/// Wrapper type for displaying the error of a report.
+struct Root<'a, E, F> {
+    report: ReportRef<'a, E, F>,
+    /// The number of consecutive errors with the same message that this error represents.
+    repeat: usize,
+}
+
+impl<E, F> Clone for Root<'_, E, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E, F> Copy for Root<'_, E, F> {}
+
+impl<E, F> fmt::Display for Root<'_, E, F>
+where
+    E: Error,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error = &self.report.error;
+        let mut linked = Linked::new(&mut *f, self.report.options.hyperlinks);
+        let mut sanitized = Sanitized {
+            inner: &mut linked,
+            enabled: self.report.options.sanitize,
+        };
+        if self.report.options.capitalize_messages {
+            write!(Capitalized::new(&mut sanitized), "{error}")?;
         } else {
-            self.fmt_singleline(f)
+            write!(sanitized, "{error}")?;
         }
+        linked.finish()?;
+        write_repeat(f, self.repeat)
     }
 }
 
-// This type intentionally outputs the same format for `Display` and `Debug`for
-// situations where you unwrap a `Report` or return it from main.
-impl<E> fmt::Debug for Report<E>
+/// The styles of a theme that are applied to a report.
+#[derive(Copy, Clone, Default)]
+struct ThemeRef<'a> {
+    header: &'a str,
+    index: &'a str,
+    message: &'a str,
+    root_cause: &'a str,
+}
+
+/// Wrapper type for styling the inner value with ANSI escape sequences.
+///
+/// If the style is empty, the inner value is printed unchanged.
+struct Styled<'a, D> {
+    inner: D,
+    style: &'a str,
+}
+
+impl<'a, D> Styled<'a, D> {
+    fn new(inner: D, style: &'a str) -> Self {
+        Styled { inner, style }
+    }
+}
+
+impl<D> fmt::Display for Styled<'_, D>
 where
-    Report<E>: fmt::Display,
+    D: fmt::Display,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(self, f)
+        if self.style.is_empty() {
+            write!(f, "{}", self.inner)
+        } else {
+            write!(f, "\x1b[{}m{}\x1b[0m", self.style, self.inner)
+        }
+    }
+}
+
+/// Writer that stops accepting characters once a limit is reached.
+struct Limit {
+    out: String,
+    /// The number of bytes that can still be written.
+    bytes: usize,
+    /// Whether the limit was reached.
+    truncated: bool,
+}
+
+/// The marker that is appended to reports that exceed their maximum length.
+const TRUNCATED: &str = "… (truncated)";
+
+impl Write for Limit {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c.len_utf8() > self.bytes {
+                self.truncated = true;
+                return Err(fmt::Error);
+            }
+            self.out.push(c);
+            self.bytes -= c.len_utf8();
+        }
+        Ok(())
+    }
+}
+
+/// Truncates `out` such that it fits into `max` bytes together with the truncation marker.
+///
+/// `out` is only cut between characters and complete escape sequences, and never between the
+/// `\r` and `\n` of a line break. Styles and hyperlinks that are active at the cut are closed.
+/// If the marker does not fit, the output is truncated without it.
+fn truncate_output(out: &mut String, max: usize) {
+    // The positions at which `out` can be cut and the escape sequences that must follow them.
+    let mut cuts = vec![(0, "")];
+    let mut styled = false;
+    let mut linked = false;
+    let mut pos = 0;
+    while pos < out.len() {
+        let rest = &out[pos..];
+        let len = match escape_len(rest) {
+            // The last escape sequence might be incomplete.
+            Some(len) if pos + len == out.len() => break,
+            Some(len) => {
+                let sequence = &rest[..len];
+                if let Some(params) = sequence
+                    .strip_prefix("\x1b[")
+                    .and_then(|s| s.strip_suffix('m'))
+                {
+                    styled = !matches!(params, "" | "0");
+                } else if let Some(link) = sequence.strip_prefix("\x1b]8;") {
+                    let uri = link.split_once(';').map_or("", |(_, uri)| uri);
+                    linked = !matches!(uri, "\x07" | "\x1b\\");
+                }
+                len
+            }
+            None => rest.chars().next().map_or(1, char::len_utf8),
+        };
+        pos += len;
+        if !rest.starts_with('\r') {
+            let close = match (linked, styled) {
+                (true, true) => "\x1b]8;;\x1b\\\x1b[0m",
+                (true, false) => "\x1b]8;;\x1b\\",
+                (false, true) => "\x1b[0m",
+                (false, false) => "",
+            };
+            cuts.push((pos, close));
+        }
+    }
+    let fits = |marker: &str| {
+        cuts.iter()
+            .rev()
+            .find(|(cut, close)| cut + close.len() + marker.len() <= max)
+            .copied()
+    };
+    let (cut, close, marker) = match fits(TRUNCATED) {
+        Some((cut, close)) => (cut, close, TRUNCATED),
+        None => {
+            let (cut, close) = fits("").unwrap_or_default();
+            (cut, close, "")
+        }
+    };
+    out.truncate(cut);
+    out.push_str(close);
+    out.push_str(marker);
+}
+
+/// Writes `template` with `{index}` and `{message}` replaced by `index` and `message`.
+fn write_template(
+    f: &mut fmt::Formatter<'_>,
+    template: &str,
+    index: &dyn fmt::Display,
+    message: &dyn fmt::Display,
+) -> fmt::Result {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        f.write_str(&rest[..start])?;
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("{index}") {
+            write!(f, "{index}")?;
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{message}") {
+            write!(f, "{message}")?;
+            rest = after;
+        } else {
+            f.write_char('{')?;
+            rest = &rest[1..];
+        }
+    }
+    f.write_str(rest)
+}
+
+/// Pads `s` to the width of `f` without truncating it to the precision of `f`.
+fn pad(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    let padding = f.width().unwrap_or(0).saturating_sub(width_of(s));
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    for _ in 0..before {
+        f.write_char(f.fill())?;
+    }
+    f.write_str(s)?;
+    for _ in 0..after {
+        f.write_char(f.fill())?;
+    }
+    Ok(())
+}
+
+/// Writes the number of times a message was repeated if it is greater than one.
+fn write_repeat(f: &mut fmt::Formatter<'_>, repeat: usize) -> fmt::Result {
+    if repeat > 1 {
+        write!(f, " (x{repeat})")?;
+    }
+    Ok(())
+}
+
+/// Writer that discards everything after the first `remaining` characters.
+struct Truncated<'a, T: ?Sized> {
+    inner: &'a mut T,
+    remaining: Option<usize>,
+    truncated: bool,
+}
+
+impl<T> Write for Truncated<'_, T>
+where
+    T: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let Some(remaining) = &mut self.remaining else {
+            return self.inner.write_str(s);
+        };
+        match s.char_indices().nth(*remaining) {
+            Some((pos, _)) => {
+                *remaining = 0;
+                self.truncated = true;
+                self.inner.write_str(&s[..pos])
+            }
+            None => {
+                *remaining -= s.chars().count();
+                self.inner.write_str(s)
+            }
+        }
+    }
+}
+
+/// Writer that escapes control characters other than line breaks if it is enabled.
+struct Sanitized<'a, T: ?Sized> {
+    inner: &'a mut T,
+    enabled: bool,
+}
+
+impl<T> Write for Sanitized<'_, T>
+where
+    T: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.enabled {
+            return self.inner.write_str(s);
+        }
+        let mut rest = s;
+        while let Some(pos) = rest.find(|c: char| c.is_control() && c != '\n') {
+            self.inner.write_str(&rest[..pos])?;
+            let c = rest[pos..].chars().next().unwrap();
+            write!(self.inner, "{}", c.escape_debug())?;
+            rest = &rest[pos + c.len_utf8()..];
+        }
+        self.inner.write_str(rest)
+    }
+}
+
+/// Writer that turns URLs into OSC 8 hyperlinks if it is enabled.
+///
+/// If enabled, the output is buffered until [`Linked::finish`] is called so that URLs that are
+/// split across multiple writes are detected.
+struct Linked<'a, T: ?Sized> {
+    inner: &'a mut T,
+    buffer: Option<String>,
+}
+
+impl<'a, T> Linked<'a, T>
+where
+    T: Write + ?Sized,
+{
+    fn new(inner: &'a mut T, enabled: bool) -> Self {
+        Linked {
+            inner,
+            buffer: enabled.then(String::new),
+        }
+    }
+
+    /// Writes the buffered output.
+    fn finish(self) -> fmt::Result {
+        let Some(buffer) = self.buffer else {
+            return Ok(());
+        };
+        let mut rest = &*buffer;
+        while let Some(start) = find_url(rest) {
+            self.inner.write_str(&rest[..start])?;
+            rest = &rest[start..];
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let url = rest[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '\'', '"']);
+            write!(self.inner, "\x1b]8;;{url}\x1b\\{url}\x1b]8;;\x1b\\")?;
+            rest = &rest[url.len()..];
+        }
+        self.inner.write_str(rest)
+    }
+}
+
+impl<T> Write for Linked<'_, T>
+where
+    T: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        match &mut self.buffer {
+            Some(buffer) => {
+                buffer.push_str(s);
+                Ok(())
+            }
+            None => self.inner.write_str(s),
+        }
+    }
+}
+
+/// Returns the position of the first `http://` or `https://` URL in `s`.
+fn find_url(s: &str) -> Option<usize> {
+    let http = s.find("http://");
+    let https = s.find("https://");
+    match (http, https) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Writer that turns line feeds into CRLF line breaks if it is enabled.
+///
+/// Line feeds that are already preceded by a carriage return are not changed.
+struct Crlf<'a, T: ?Sized> {
+    inner: &'a mut T,
+    enabled: bool,
+    /// Whether the last character that was written is a carriage return.
+    after_cr: bool,
+}
+
+impl<T> Write for Crlf<'_, T>
+where
+    T: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.enabled {
+            return self.inner.write_str(s);
+        }
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                if !self.after_cr {
+                    self.inner.write_char('\r')?;
+                }
+                self.inner.write_char('\n')?;
+                self.after_cr = false;
+            }
+            if !line.is_empty() {
+                self.inner.write_str(line)?;
+                self.after_cr = line.ends_with('\r');
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writer that replaces line breaks with the replacement, if any.
+///
+/// A carriage return that directly precedes a line feed is removed as well.
+struct Flattened<'a, T: ?Sized> {
+    inner: &'a mut T,
+    replacement: Option<&'a str>,
+}
+
+impl<T> Write for Flattened<'_, T>
+where
+    T: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let Some(replacement) = self.replacement else {
+            return self.inner.write_str(s);
+        };
+        let mut lines = s.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            if lines.peek().is_some() {
+                self.inner
+                    .write_str(line.strip_suffix('\r').unwrap_or(line))?;
+                self.inner.write_str(replacement)?;
+            } else {
+                self.inner.write_str(line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Wrapper type for upper-casing the first character of the inner source.
+struct Capitalized<'a, D: ?Sized> {
+    inner: &'a mut D,
+    done: bool,
+}
+
+impl<'a, D: ?Sized> Capitalized<'a, D> {
+    fn new(inner: &'a mut D) -> Self {
+        Capitalized { inner, done: false }
+    }
+}
+
+impl<T> Write for Capitalized<'_, T>
+where
+    T: Write + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.done {
+            return self.inner.write_str(s);
+        }
+        let mut chars = s.chars();
+        let Some(first) = chars.next() else {
+            return Ok(());
+        };
+        self.done = true;
+        for c in first.to_uppercase() {
+            self.inner.write_char(c)?;
+        }
+        self.inner.write_str(chars.as_str())
     }
 }
 
 /// Wrapper type for indenting the inner source.
 struct Indented<'a, D> {
     inner: &'a mut D,
+    indent: &'a str,
 }
 
 impl<T> Write for Indented<'_, T>
@@ -436,7 +3821,7 @@ where
         for (i, line) in s.split('\n').enumerate() {
             if i > 0 {
                 self.inner.write_char('\n')?;
-                self.inner.write_str("      ")?;
+                self.inner.write_str(self.indent)?;
             }
 
             self.inner.write_str(line)?;
@@ -446,29 +3831,140 @@ where
     }
 }
 
-/// An iterator over an [`Error`] and its sources.
+/// Wrapper type for HTML-escaping the inner source.
+struct HtmlEscaped<'a, D> {
+    inner: &'a mut D,
+}
+
+impl<T> Write for HtmlEscaped<'_, T>
+where
+    T: Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some(pos) = rest.find(['&', '<', '>', '"', '\'']) {
+            self.inner.write_str(&rest[..pos])?;
+            self.inner.write_str(match rest.as_bytes()[pos] {
+                b'&' => "&amp;",
+                b'<' => "&lt;",
+                b'>' => "&gt;",
+                b'"' => "&quot;",
+                _ => "&#39;",
+            })?;
+            rest = &rest[pos + 1..];
+        }
+        self.inner.write_str(rest)
+    }
+}
+
+/// Wrapper type for escaping Markdown in the inner source.
+struct MarkdownEscaped<'a, D> {
+    inner: &'a mut D,
+}
+
+impl<T> Write for MarkdownEscaped<'_, T>
+where
+    T: Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut rest = s;
+        while let Some(pos) = rest.find(['\\', '*', '_', '~', '`', '|', '[', ']']) {
+            self.inner.write_str(&rest[..pos])?;
+            self.inner.write_char('\\')?;
+            self.inner.write_str(&rest[pos..pos + 1])?;
+            rest = &rest[pos + 1..];
+        }
+        self.inner.write_str(rest)
+    }
+}
+
+/// Returns an iterator over an [`Error`] and its sources.
 ///
 /// If you want to omit the initial error and only process
 /// its sources, use `skip(1)`.
-#[derive(Clone, Debug)]
-struct Source<'a> {
-    current: Option<&'a (dyn Error + 'static)>,
+///
+/// The iterator stops before yielding an error a second time, so it terminates even if the
+/// source chain forms a cycle. Errors are compared by address.
+fn source_chain<'a>(
+    error: &'a (dyn Error + 'static),
+) -> impl Iterator<Item = &'a (dyn Error + 'static)> + Clone {
+    std::iter::once(error).chain(sources_after(address(error), error.source()))
+}
+
+/// Returns an iterator over `first` and its sources.
+///
+/// `root` is the address of the error whose source is `first`. Like [`source_chain`], the
+/// iterator stops before yielding an error a second time or reaching `root` again.
+fn sources_after<'a>(
+    root: *const (),
+    first: Option<&'a (dyn Error + 'static)>,
+) -> impl Iterator<Item = &'a (dyn Error + 'static)> + Clone {
+    let (len, _) = chain_len(root, first);
+    std::iter::successors(first, |&error| error.source()).take(len)
+}
+
+/// Returns whether the sources of the error at `root`, starting at `first`, form a cycle.
+fn has_cycle(root: *const (), first: Option<&(dyn Error + 'static)>) -> bool {
+    chain_len(root, first).1
 }
 
-impl<'a> Source<'a> {
-    pub fn new(error: &'a (dyn Error + 'static)) -> Self {
-        Self {
-            current: Some(error),
+/// Returns the number of errors in the chain starting at `first` before an error repeats, and
+/// whether an error repeats.
+///
+/// Reaching `root`, the address of the error whose source is `first`, counts as a repetition.
+/// Cycles are detected with Brent's algorithm, which walks the chain a constant number of
+/// times and does not allocate.
+fn chain_len<'a>(root: *const (), first: Option<&'a (dyn Error + 'static)>) -> (usize, bool) {
+    let next = |error: Option<&'a (dyn Error + 'static)>| error.and_then(|error| error.source());
+    // The end of an acyclic chain is treated as a cycle of length 1 at `None`.
+    let mut power = 1;
+    let mut cycle = 1;
+    let mut tortoise = first;
+    let mut hare = next(first);
+    while !same_error(tortoise, hare) {
+        if power == cycle {
+            tortoise = hare;
+            power *= 2;
+            cycle = 0;
         }
+        hare = next(hare);
+        cycle += 1;
+    }
+
+    let mut tortoise = first;
+    let mut hare = first;
+    for _ in 0..cycle {
+        hare = next(hare);
+    }
+    let mut start = 0;
+    while !same_error(tortoise, hare) {
+        tortoise = next(tortoise);
+        hare = next(hare);
+        start += 1;
     }
+    if tortoise.is_none() {
+        return (start, false);
+    }
+
+    let len = start + cycle;
+    let root = std::iter::successors(first, |&error| error.source())
+        .take(len)
+        .position(|error| size_of_val(error) != 0 && address(error) == root);
+    (root.unwrap_or(len), true)
 }
 
-impl<'a> Iterator for Source<'a> {
-    type Item = &'a (dyn Error + 'static);
+/// Returns the address of `error`.
+fn address(error: &dyn Error) -> *const () {
+    error as *const dyn Error as *const ()
+}
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let current = self.current;
-        self.current = self.current.and_then(Error::source);
-        current
+/// Returns whether `a` and `b` are the same error.
+///
+/// Zero-sized errors do not have unique addresses, so their vtables are compared as well.
+fn same_error(a: Option<&dyn Error>, b: Option<&dyn Error>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) if size_of_val(a) == 0 => std::ptr::eq(a, b),
+        (Some(a), Some(b)) => address(a) == address(b),
+        (a, b) => a.is_none() && b.is_none(),
     }
 }
@@ -0,0 +1,41 @@
+use crate::{Dynamic, Report, ReportFormat};
+use std::borrow::Cow;
+use std::error::Error;
+use std::vec;
+
+/// An iterator over the lines of a formatted [`Report`].
+///
+/// This type is created by [`Report::as_lines`]. The report is formatted when the first line is
+/// requested. Lines do not contain the trailing line break.
+///
+/// The lines are currently always returned as [`Cow::Owned`].
+pub struct LinesReport<'a, E, F = Dynamic> {
+    report: &'a Report<E, F>,
+    lines: Option<vec::IntoIter<String>>,
+}
+
+impl<'a, E, F> LinesReport<'a, E, F> {
+    pub(crate) fn new(report: &'a Report<E, F>) -> Self {
+        LinesReport {
+            report,
+            lines: None,
+        }
+    }
+}
+
+impl<'a, E, F> Iterator for LinesReport<'a, E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    type Item = Cow<'a, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let report = self.report;
+        let lines = self.lines.get_or_insert_with(|| {
+            let lines: Vec<_> = report.to_string().split('\n').map(str::to_owned).collect();
+            lines.into_iter()
+        });
+        lines.next().map(Cow::Owned)
+    }
+}
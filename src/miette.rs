@@ -0,0 +1,60 @@
+use crate::{address, sources_after, ChainedError, Report};
+use miette::Diagnostic;
+
+impl Report<ChainedError> {
+    /// Create a report from a [`miette::Diagnostic`].
+    ///
+    /// The causes of the report are the sources of the diagnostic, followed by its related
+    /// diagnostics and their sources. If the diagnostic has help text, it is added as the last
+    /// cause with the prefix `help: `.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    /// use std::fmt;
+    ///
+    /// #[derive(Debug)]
+    /// struct ConfigError;
+    ///
+    /// impl fmt::Display for ConfigError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.write_str("could not load config")
+    ///     }
+    /// }
+    ///
+    /// impl std::error::Error for ConfigError {}
+    ///
+    /// impl miette::Diagnostic for ConfigError {
+    ///     fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+    ///         Some(Box::new("create a config file"))
+    ///     }
+    /// }
+    ///
+    /// let report = Report::from_diagnostic(ConfigError);
+    /// assert_eq!(report.to_string(), "could not load config: help: create a config file");
+    /// ```
+    pub fn from_diagnostic<D>(diagnostic: D) -> Self
+    where
+        D: Diagnostic,
+    {
+        let mut causes = Vec::new();
+        let sources = sources_after(address(&diagnostic), diagnostic.source());
+        causes.extend(sources.map(|e| e.to_string()));
+        collect_related(&diagnostic, &mut causes);
+        if let Some(help) = diagnostic.help() {
+            causes.push(format!("help: {help}"));
+        }
+        Report::new(ChainedError::new(diagnostic.to_string(), causes))
+    }
+}
+
+/// Append the messages of the related diagnostics of `diagnostic` and their sources.
+fn collect_related(diagnostic: &dyn Diagnostic, causes: &mut Vec<String>) {
+    for related in diagnostic.related().into_iter().flatten() {
+        causes.push(related.to_string());
+        let sources = sources_after(address(related), related.source());
+        causes.extend(sources.map(|e| e.to_string()));
+        collect_related(related, causes);
+    }
+}
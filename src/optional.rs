@@ -0,0 +1,72 @@
+use crate::Report;
+use std::error::Error;
+use std::fmt;
+
+/// An error that might not be present.
+///
+/// If the error is absent, the message is `no error` and there is no source. Otherwise the
+/// message and the source are those of the inner error.
+///
+/// This type is used to create reports from an `Option<E>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::Report;
+///
+/// let error: Option<std::io::Error> = None;
+/// assert_eq!(Report::from(error).to_string(), "no error");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionalError<E>(pub Option<E>);
+
+impl<E> fmt::Display for OptionalError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(error) => error.fmt(f),
+            None => f.write_str("no error"),
+        }
+    }
+}
+
+impl<E> Error for OptionalError<E>
+where
+    E: Error,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.as_ref().and_then(|e| e.source())
+    }
+}
+
+impl<E> From<Option<E>> for Report<OptionalError<E>>
+where
+    E: Error,
+{
+    fn from(error: Option<E>) -> Self {
+        Report::from(OptionalError(error))
+    }
+}
+
+impl<E> Report<OptionalError<E>>
+where
+    E: Error,
+{
+    /// Create a report from an error that might not be present.
+    ///
+    /// If `error` is `None`, the report prints `no error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = Some(std::io::Error::other("could not load config"));
+    /// assert_eq!(Report::from_option(error).to_string(), "could not load config");
+    /// ```
+    pub fn from_option(error: Option<E>) -> Self {
+        Self::from(error)
+    }
+}
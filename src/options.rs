@@ -0,0 +1,419 @@
+use std::any::type_name;
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+/// The options that control how a [`Report`](crate::Report) is formatted.
+///
+/// Each option corresponds to a builder method on `Report` with the same name. The options can
+/// be applied to a report at once with [`Report::options`](crate::Report::options).
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct FormatOptions {
+    /// Whether the report should be pretty-printed.
+    pub pretty: bool,
+    /// Whether the report should be formatted as a JSON object.
+    pub json: bool,
+    /// Whether the report should be formatted like the errors printed by Cargo.
+    pub cargo_style: bool,
+    /// Whether the report should be formatted like the diagnostics printed by rustc.
+    pub rustc_style: bool,
+    /// The footer that is printed after the causes in pretty mode.
+    pub footer: Option<Cow<'static, str>>,
+    /// The name of the program that is printed before the report.
+    pub program_name: Option<Cow<'static, str>>,
+    /// The title that is printed before the report.
+    pub title_prefix: Option<Cow<'static, str>>,
+    /// Whether the report should be formatted as Markdown for chat messages.
+    pub markdown: bool,
+    /// The code printed in the header of rustc-style reports.
+    pub error_code: Option<Cow<'static, str>>,
+    /// Whether the sources should be drawn as a tree in pretty mode.
+    pub tree: bool,
+    /// The characters used to draw the tree.
+    pub box_style: BoxStyle,
+    /// The title of the panel drawn around the report, or `None` to not draw a panel.
+    pub panel: Option<Cow<'static, str>>,
+    /// Whether each cause should be indented below its parent in pretty mode.
+    pub nested: bool,
+    /// Whether each cause should be printed behind the header without alignment in pretty mode.
+    pub plain: bool,
+    /// Whether the kind of `std::io::Error` causes should be printed.
+    pub show_io_error_kind: bool,
+    /// Whether the report should be pretty-printed as an HTML list.
+    pub html_list: bool,
+    /// The HTML tag that wraps the error in an HTML list.
+    ///
+    /// `p` is used if this is not an ASCII-alphanumeric element name.
+    pub html_root_tag: String,
+    /// When to number the causes in pretty mode.
+    pub cause_numbering: CauseNumbering,
+    /// The string printed between the messages in single-line mode.
+    pub separator: Cow<'static, str>,
+    /// Whether the causes are grouped in parentheses in single-line mode.
+    pub parenthesized: bool,
+    /// The string that replaces line breaks in single-line mode, or `None` to keep them.
+    pub newline_replacement: Option<Cow<'static, str>>,
+    /// The string printed before each cause in single-line mode.
+    pub cause_prefix: Cow<'static, str>,
+    /// The number of the first cause in pretty mode.
+    pub first_number: usize,
+    /// The minimum width of the cause numbers in pretty mode.
+    pub min_number_digits: usize,
+    /// The string printed between a cause number and the cause in pretty mode.
+    pub number_separator: Cow<'static, str>,
+    /// The indentation of causes in pretty mode, or `None` to align them with the numbers.
+    pub indentation: Option<Cow<'static, str>>,
+    /// Whether a blank line is printed before the causes in pretty mode.
+    pub blank_line_before_causes: bool,
+    /// The header printed before the causes in pretty mode.
+    pub caused_by_label: Cow<'static, str>,
+    /// The labels that replace the built-in English labels.
+    #[cfg(feature = "i18n")]
+    pub(crate) labels: Option<std::sync::Arc<dyn crate::Labels>>,
+    /// Whether line breaks are printed as `\r\n`.
+    pub crlf: bool,
+    /// The string printed at the start of every line.
+    pub line_prefix: Option<Cow<'static, str>>,
+    /// The template of the first line in pretty mode.
+    pub header_template: Option<Cow<'static, str>>,
+    /// The template of the cause lines in pretty mode.
+    pub cause_template: Option<Cow<'static, str>>,
+    /// Whether the type names of the causes should be printed.
+    pub show_cause_types: bool,
+    /// The registered types whose names can be printed in addition to the built-in types.
+    pub(crate) cause_types: Vec<CauseType>,
+    /// What happens when the source chain forms a cycle.
+    pub on_cycle: CycleBehavior,
+    /// Whether the first character of each message should be upper-cased.
+    pub capitalize_messages: bool,
+    /// Whether the causes should be printed as a bullet list in pretty mode.
+    pub use_bullet_list: bool,
+    /// The bullet used in bullet lists.
+    pub bullet: char,
+    /// Whether the innermost cause is printed first.
+    pub reverse: bool,
+    /// Whether only the innermost cause is printed.
+    pub root_only: bool,
+    /// Whether control characters in messages are escaped.
+    pub sanitize: bool,
+    /// Whether causes contained in the message of the preceding error are omitted.
+    pub suppress_embedded: bool,
+    /// Whether consecutive identical messages are merged.
+    pub collapse_duplicates: bool,
+    /// The maximum number of causes that are printed.
+    pub max_causes: Option<usize>,
+    /// The maximum length of the message of each cause in characters.
+    pub max_cause_len: Option<usize>,
+    /// The maximum length of the formatted report in bytes.
+    pub max_len: Option<usize>,
+    /// The column at which messages are wrapped in pretty mode.
+    pub wrap_width: Option<usize>,
+    /// Whether the report should be styled with ANSI escape sequences.
+    pub colored: bool,
+    /// Whether URLs in messages are printed as OSC 8 hyperlinks.
+    pub hyperlinks: bool,
+    /// The styles used if `colored` is enabled.
+    pub theme: Theme,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            pretty: false,
+            json: false,
+            cargo_style: false,
+            rustc_style: false,
+            error_code: None,
+            markdown: false,
+            program_name: None,
+            title_prefix: None,
+            footer: None,
+            tree: false,
+            box_style: BoxStyle::default(),
+            panel: None,
+            nested: false,
+            plain: false,
+            show_io_error_kind: false,
+            html_list: false,
+            html_root_tag: "p".to_string(),
+            cause_numbering: CauseNumbering::default(),
+            separator: Cow::Borrowed(": "),
+            parenthesized: false,
+            newline_replacement: Some(Cow::Borrowed("; ")),
+            cause_prefix: Cow::Borrowed(""),
+            first_number: 0,
+            min_number_digits: 4,
+            number_separator: Cow::Borrowed(": "),
+            indentation: None,
+            blank_line_before_causes: true,
+            caused_by_label: Cow::Borrowed("Caused by:"),
+            #[cfg(feature = "i18n")]
+            labels: None,
+            crlf: false,
+            line_prefix: None,
+            header_template: None,
+            cause_template: None,
+            show_cause_types: false,
+            cause_types: Vec::new(),
+            on_cycle: CycleBehavior::default(),
+            capitalize_messages: false,
+            use_bullet_list: false,
+            bullet: '•',
+            reverse: false,
+            root_only: false,
+            sanitize: false,
+            suppress_embedded: false,
+            collapse_duplicates: false,
+            max_causes: None,
+            max_cause_len: None,
+            max_len: None,
+            wrap_width: None,
+            colored: false,
+            hyperlinks: false,
+            theme: Theme::default(),
+        }
+    }
+}
+
+/// A type whose name can be printed.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CauseType {
+    pub(crate) is: fn(&(dyn Error + 'static)) -> bool,
+    pub(crate) name: fn() -> &'static str,
+}
+
+impl CauseType {
+    pub(crate) const fn of<T>() -> Self
+    where
+        T: Error + 'static,
+    {
+        CauseType {
+            is: is::<T>,
+            name: type_name::<T>,
+        }
+    }
+}
+
+/// Returns whether `error` is of type `T`.
+fn is<T>(error: &(dyn Error + 'static)) -> bool
+where
+    T: Error + 'static,
+{
+    error.is::<T>()
+}
+
+/// The types whose names can always be printed.
+pub(crate) static BUILTIN_CAUSE_TYPES: &[CauseType] = &[
+    CauseType::of::<std::io::Error>(),
+    CauseType::of::<fmt::Error>(),
+    CauseType::of::<std::num::ParseIntError>(),
+    CauseType::of::<std::num::ParseFloatError>(),
+    CauseType::of::<std::num::TryFromIntError>(),
+    CauseType::of::<std::str::ParseBoolError>(),
+    CauseType::of::<std::str::Utf8Error>(),
+    CauseType::of::<std::string::FromUtf8Error>(),
+    CauseType::of::<std::char::ParseCharError>(),
+    CauseType::of::<crate::ChainedError>(),
+];
+
+/// When to number the causes in pretty mode.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CauseNumbering {
+    /// Number the causes if there is more than one cause.
+    #[default]
+    Auto,
+    /// Always number the causes.
+    Always,
+    /// Never number the causes.
+    Never,
+}
+
+/// What happens when the source chain of a report forms a cycle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CycleBehavior {
+    /// Print the given string after the last cause.
+    Annotate(Cow<'static, str>),
+    /// Stop printing causes at the first repeated cause.
+    Stop,
+    /// Panic with a message that contains the causes up to the first repeated cause.
+    Panic,
+}
+
+impl Default for CycleBehavior {
+    fn default() -> Self {
+        CycleBehavior::Annotate(Cow::Borrowed("<cycle detected>"))
+    }
+}
+
+/// The ANSI styles used by colored reports.
+///
+/// Each style is a list of SGR parameters separated by `;`, for example `1;31` for bold red
+/// text. An empty string disables styling for that element.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Theme {
+    /// The style of the `Caused by:` header. The default is bold.
+    pub header: Cow<'static, str>,
+    /// The style of the cause numbers and bullets. The default is yellow.
+    pub index: Cow<'static, str>,
+    /// The style of the messages of the error and its causes. The default is unstyled.
+    pub message: Cow<'static, str>,
+    /// The style of the message of the deepest cause in pretty mode, or an empty string to use
+    /// the style of the other messages. The default is empty.
+    pub root_cause: Cow<'static, str>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            header: Cow::Borrowed("1"),
+            index: Cow::Borrowed("33"),
+            message: Cow::Borrowed(""),
+            root_cause: Cow::Borrowed(""),
+        }
+    }
+}
+
+impl Theme {
+    /// A theme with 256-color styles for terminals with a dark background.
+    pub const fn dark() -> Self {
+        Theme {
+            header: Cow::Borrowed("1;38;5;203"),
+            index: Cow::Borrowed("38;5;221"),
+            message: Cow::Borrowed("38;5;252"),
+            root_cause: Cow::Borrowed(""),
+        }
+    }
+
+    /// A theme with 256-color styles for terminals with a light background.
+    pub const fn light() -> Self {
+        Theme {
+            header: Cow::Borrowed("1;38;5;124"),
+            index: Cow::Borrowed("38;5;130"),
+            message: Cow::Borrowed("38;5;236"),
+            root_cause: Cow::Borrowed(""),
+        }
+    }
+
+    /// A theme with 24-bit colors for terminals that support them.
+    pub const fn truecolor() -> Self {
+        Theme {
+            header: Cow::Borrowed("1;38;2;255;95;95"),
+            index: Cow::Borrowed("38;2;255;215;95"),
+            message: Cow::Borrowed(""),
+            root_cause: Cow::Borrowed(""),
+        }
+    }
+
+    /// A theme that only uses the 16 basic colors, for terminals with limited color support.
+    pub const fn basic() -> Self {
+        Theme {
+            header: Cow::Borrowed("1;31"),
+            index: Cow::Borrowed("33"),
+            message: Cow::Borrowed(""),
+            root_cause: Cow::Borrowed(""),
+        }
+    }
+
+    /// Remove the style of the messages.
+    ///
+    /// Only the header and the cause numbers remain styled. This keeps messages that are copied
+    /// from the terminal free of escape sequences. The default theme does not style messages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::{Report, Theme};
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let report = Report::new(error)
+    ///     .colored(true)
+    ///     .theme(Theme::dark().plain_messages());
+    /// assert_eq!(report.to_string(), "could not load config");
+    /// ```
+    pub fn plain_messages(mut self) -> Self {
+        self.message = Cow::Borrowed("");
+        self.root_cause = Cow::Borrowed("");
+        self
+    }
+}
+
+/// The characters used to draw the tree in tree mode.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum BoxStyle {
+    /// Unicode box-drawing characters: `├──`, `└──`, and `│`.
+    #[default]
+    Unicode,
+    /// ASCII characters: `+--`, `+--`, and `|`.
+    Ascii,
+}
+
+impl BoxStyle {
+    /// Choose a style based on the `TERM` environment variable.
+    ///
+    /// Returns [`BoxStyle::Ascii`] if `TERM` is unset, empty, `dumb`, or names a terminal that
+    /// predates Unicode (`vt*`, `ansi*`). Returns [`BoxStyle::Unicode`] otherwise.
+    pub fn auto() -> Self {
+        match std::env::var("TERM") {
+            Ok(term) if supports_unicode(&term) => BoxStyle::Unicode,
+            _ => BoxStyle::Ascii,
+        }
+    }
+
+    pub(crate) fn tee(self) -> &'static str {
+        match self {
+            BoxStyle::Unicode => "├── ",
+            BoxStyle::Ascii => "+-- ",
+        }
+    }
+
+    pub(crate) fn corner(self) -> &'static str {
+        match self {
+            BoxStyle::Unicode => "└── ",
+            BoxStyle::Ascii => "+-- ",
+        }
+    }
+
+    pub(crate) fn panel(self) -> PanelChars {
+        match self {
+            BoxStyle::Unicode => PanelChars {
+                top_left: "╭",
+                top_right: "╮",
+                bottom_left: "╰",
+                bottom_right: "╯",
+                horizontal: "─",
+                vertical: "│",
+            },
+            BoxStyle::Ascii => PanelChars {
+                top_left: "+",
+                top_right: "+",
+                bottom_left: "+",
+                bottom_right: "+",
+                horizontal: "-",
+                vertical: "|",
+            },
+        }
+    }
+
+    pub(crate) fn continuation(self) -> &'static str {
+        match self {
+            BoxStyle::Unicode => "│   ",
+            BoxStyle::Ascii => "|   ",
+        }
+    }
+}
+
+/// The characters used to draw a panel.
+pub(crate) struct PanelChars {
+    pub(crate) top_left: &'static str,
+    pub(crate) top_right: &'static str,
+    pub(crate) bottom_left: &'static str,
+    pub(crate) bottom_right: &'static str,
+    pub(crate) horizontal: &'static str,
+    pub(crate) vertical: &'static str,
+}
+
+fn supports_unicode(term: &str) -> bool {
+    !(term.is_empty() || term == "dumb" || term.starts_with("vt") || term.starts_with("ansi"))
+}
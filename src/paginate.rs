@@ -0,0 +1,184 @@
+use crate::wrap::{escape_len, width_of};
+use crate::{Report, ReportFormat};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::{cursor, queue, terminal};
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+
+/// Display the report in the terminal one screen at a time.
+///
+/// The following keys are supported:
+///
+/// - `q`, `Esc`, or `Ctrl-C`: quit
+/// - `j` or `Down`: scroll down one line
+/// - `k` or `Up`: scroll up one line
+/// - `Space` or `PageDown`: scroll down one page
+/// - `b` or `PageUp`: scroll up one page
+///
+/// If stdout is not a terminal or if the report fits on a single screen, the report is printed
+/// to stdout without a pager.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use error_reporter::{paginate, Report};
+///
+/// let error = std::io::Error::other("could not load config");
+/// paginate(&Report::new(error).pretty(true)).unwrap();
+/// ```
+pub fn paginate<E, F>(report: &Report<E, F>) -> io::Result<()>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    let text = report.to_string();
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut stdout = io::stdout();
+    if stdout.is_terminal() {
+        let (columns, rows) = terminal::size()?;
+        let page = rows.saturating_sub(1) as usize;
+        if lines.len() > page {
+            return run(
+                &mut stdout,
+                &lines,
+                Pager::new(lines.len(), page),
+                columns as usize,
+            );
+        }
+    }
+    writeln!(stdout, "{text}")
+}
+
+/// Runs the pager until the user quits.
+fn run(out: &mut impl Write, lines: &[&str], mut pager: Pager, columns: usize) -> io::Result<()> {
+    let _guard = Guard::new()?;
+    loop {
+        draw(out, lines, &pager, columns)?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+        let c = match key.code {
+            // Raw mode turns Ctrl-C into a key event instead of a signal.
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => 'q',
+            KeyCode::Char(c) => c,
+            KeyCode::Esc => 'q',
+            KeyCode::Down => 'j',
+            KeyCode::Up => 'k',
+            KeyCode::PageDown => ' ',
+            KeyCode::PageUp => 'b',
+            _ => continue,
+        };
+        if !pager.handle(c) {
+            return Ok(());
+        }
+    }
+}
+
+/// Draws the visible lines followed by a status line.
+fn draw(out: &mut impl Write, lines: &[&str], pager: &Pager, columns: usize) -> io::Result<()> {
+    queue!(out, terminal::Clear(terminal::ClearType::All))?;
+    let visible = lines.iter().skip(pager.top).take(pager.page);
+    for (row, line) in visible.enumerate() {
+        let line = truncate(line, columns);
+        queue!(out, cursor::MoveTo(0, row as u16), Print(line))?;
+    }
+    let last = (pager.top + pager.page).min(pager.len);
+    let status = format!(
+        "lines {}-{last} of {} (q to quit)",
+        pager.top + 1,
+        pager.len
+    );
+    queue!(
+        out,
+        cursor::MoveTo(0, pager.page as u16),
+        SetAttribute(Attribute::Reverse),
+        Print(status),
+        SetAttribute(Attribute::Reset),
+    )?;
+    out.flush()
+}
+
+/// Truncates `line` to `columns` columns.
+///
+/// Escape sequences take up no space and are kept even after the last visible character, so
+/// that styles and hyperlinks that are closed later in the line do not leak into the rest of the
+/// screen.
+pub(crate) fn truncate(line: &str, columns: usize) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut width = 0;
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if let Some(len) = escape_len(rest) {
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+        let w = width_of(c.encode_utf8(&mut [0; 4]));
+        if width + w <= columns {
+            width += w;
+            out.push(c);
+        } else {
+            width = columns;
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Switches the terminal to the alternate screen and restores it when dropped.
+struct Guard;
+
+impl Guard {
+    fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let guard = Guard;
+        crossterm::execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// The scroll position of the pager.
+pub(crate) struct Pager {
+    /// The index of the first visible line.
+    pub(crate) top: usize,
+    /// The number of visible lines.
+    pub(crate) page: usize,
+    /// The total number of lines.
+    pub(crate) len: usize,
+}
+
+impl Pager {
+    pub(crate) fn new(len: usize, page: usize) -> Self {
+        Pager {
+            top: 0,
+            page: page.max(1),
+            len,
+        }
+    }
+
+    /// Updates the scroll position. Returns `false` if the pager should quit.
+    pub(crate) fn handle(&mut self, key: char) -> bool {
+        let max = self.len.saturating_sub(self.page);
+        self.top = match key {
+            'q' => return false,
+            'j' => self.top + 1,
+            'k' => self.top.saturating_sub(1),
+            ' ' => self.top + self.page,
+            'b' => self.top.saturating_sub(self.page),
+            _ => self.top,
+        }
+        .min(max);
+        true
+    }
+}
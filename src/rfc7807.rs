@@ -0,0 +1,40 @@
+use crate::json::JsonString;
+use crate::{Report, ReportFormat};
+use std::error::Error;
+
+impl<E, F> Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    /// Format the report as an [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807) problem
+    /// details object.
+    ///
+    /// The message of the error is used as the `title` and the formatted report as the
+    /// `detail`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// let cause = std::io::Error::other("permission denied");
+    /// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+    /// assert_eq!(
+    ///     report.to_problem_details(500, "about:blank"),
+    ///     concat!(
+    ///         r#"{"type":"about:blank","title":"could not load config","#,
+    ///         r#""detail":"could not load config: permission denied","status":500}"#,
+    ///     ),
+    /// );
+    /// ```
+    pub fn to_problem_details(&self, status: u16, r#type: &str) -> String {
+        format!(
+            r#"{{"type":{},"title":{},"detail":{},"status":{status}}}"#,
+            JsonString(r#type),
+            JsonString(&self.borrowed().root().to_string()),
+            JsonString(&self.to_string()),
+        )
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{Dynamic, Report, ReportFormat};
+use std::error::Error;
+use std::fmt;
+
+/// The "Caused by:" section of a pretty-printed [`Report`].
+///
+/// This type is created by [`Report::cause_section`]. It formats the causes exactly like
+/// [`Report::fmt_multiline`] but without the message of the error and the blank line that
+/// follows it. If the error has no causes, nothing is printed.
+pub struct CauseSection<'a, E, F = Dynamic> {
+    report: &'a Report<E, F>,
+}
+
+impl<'a, E, F> CauseSection<'a, E, F> {
+    pub(crate) fn new(report: &'a Report<E, F>) -> Self {
+        CauseSection { report }
+    }
+}
+
+impl<E, F> fmt::Display for CauseSection<'_, E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wrap = f.width().or(self.report.options.wrap_width);
+        self.report.borrowed().fmt_causes(f, wrap)
+    }
+}
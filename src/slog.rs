@@ -0,0 +1,30 @@
+use crate::{Report, ReportFormat};
+use std::error::Error;
+
+/// Logs the formatted report as a string.
+///
+/// Since slog keys are `&'static str`, the causes are not emitted as separate key-value pairs.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::Report;
+///
+/// let logger = slog::Logger::root(slog::Discard, slog::o!());
+/// let error = std::io::Error::other("could not load config");
+/// slog::error!(logger, "request failed"; "error" => Report::new(error));
+/// ```
+impl<E, F> slog::Value for Report<E, F>
+where
+    E: Error,
+    F: ReportFormat,
+{
+    fn serialize(
+        &self,
+        _record: &slog::Record<'_>,
+        key: slog::Key,
+        serializer: &mut dyn slog::Serializer,
+    ) -> slog::Result {
+        serializer.emit_str(key, &self.to_string())
+    }
+}
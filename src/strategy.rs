@@ -0,0 +1,82 @@
+use crate::{address, sources_after};
+use std::error::Error;
+
+/// A strategy for collecting the causes of an error.
+///
+/// By default, [`Report`](crate::Report) follows [`Error::source`] until it reaches an error
+/// without a source. Implement this trait to print the causes of errors that store them
+/// elsewhere, for example, errors with multiple causes.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{CauseStrategy, Report};
+/// use std::error::Error;
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct MultiError(Vec<std::io::Error>);
+///
+/// impl fmt::Display for MultiError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "{} operations failed", self.0.len())
+///     }
+/// }
+///
+/// impl Error for MultiError {}
+///
+/// struct MultiStrategy;
+///
+/// impl CauseStrategy for MultiStrategy {
+///     fn causes<'a>(
+///         &self,
+///         error: &'a (dyn Error + 'static),
+///     ) -> Box<dyn Iterator<Item = &'a (dyn Error + 'static)> + 'a> {
+///         match error.downcast_ref::<MultiError>() {
+///             Some(multi) => Box::new(multi.0.iter().map(|e| e as _)),
+///             None => Box::new(std::iter::empty()),
+///         }
+///     }
+/// }
+///
+/// let error = MultiError(vec![
+///     std::io::Error::other("disk full"),
+///     std::io::Error::other("permission denied"),
+/// ]);
+/// let report = Report::new(error).with_strategy(MultiStrategy);
+/// assert_eq!(
+///     report.to_string(),
+///     "2 operations failed: disk full: permission denied",
+/// );
+/// ```
+pub trait CauseStrategy: Send + Sync {
+    /// Returns the causes of `error` in the order in which they should be printed.
+    ///
+    /// `error` is the error being reported. The returned iterator should not yield `error`
+    /// itself.
+    fn causes<'a>(
+        &self,
+        error: &'a (dyn Error + 'static),
+    ) -> Box<dyn Iterator<Item = &'a (dyn Error + 'static)> + 'a>;
+}
+
+/// The default strategy that follows [`Error::source`].
+///
+/// The traversal stops when a cycle is detected.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct DefaultStrategy;
+
+impl CauseStrategy for DefaultStrategy {
+    fn causes<'a>(
+        &self,
+        error: &'a (dyn Error + 'static),
+    ) -> Box<dyn Iterator<Item = &'a (dyn Error + 'static)> + 'a> {
+        Box::new(sources_after(address(error), error.source()))
+    }
+}
+
+/// A strategy together with a function that turns the reported error into a trait object.
+pub(crate) struct Strategy<E> {
+    pub(crate) strategy: Box<dyn CauseStrategy>,
+    pub(crate) upcast: fn(&E) -> &(dyn Error + 'static),
+}
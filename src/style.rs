@@ -0,0 +1,192 @@
+use crate::{FormatOptions, Report, Theme};
+use std::borrow::Cow;
+use std::error::Error;
+use std::sync::{PoisonError, RwLock};
+
+/// The style set with [`set_default_style`].
+static DEFAULT_STYLE: RwLock<Option<ReportStyle>> = RwLock::new(None);
+
+/// Set the style of reports created with [`Report::new`] or `From`.
+///
+/// This allows an application to decide once, for example after parsing its command-line
+/// arguments, how all reports are formatted. Reports that were created before this function is
+/// called are not affected. Builder methods called on a report still override the style.
+///
+/// With the `env_style` feature, reports that are created before a style is set use the style
+/// selected by the `ERROR_REPORTER_STYLE` environment variable: `compact` for single-line
+/// output, `pretty` for [pretty](Report::pretty) output, or `json` for [JSON](Report::json)
+/// output. The variable is read when the first report is created.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{set_default_style, Report, ReportStyle};
+///
+/// set_default_style(ReportStyle::new().separator(" -> "));
+///
+/// let error = std::io::Error::other("could not load config");
+/// let cause = std::io::Error::other("permission denied");
+/// let report = Report::new(error).with_causes(vec![Box::new(cause)]);
+/// assert_eq!(report.to_string(), "could not load config -> permission denied");
+/// ```
+pub fn set_default_style(style: ReportStyle) {
+    *DEFAULT_STYLE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(style);
+}
+
+/// Returns the options of the style set with [`set_default_style`].
+pub(crate) fn default_options() -> FormatOptions {
+    match &*DEFAULT_STYLE.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(style) => style.options.clone(),
+        #[cfg(feature = "env_style")]
+        None => crate::env::env_style().options.clone(),
+        #[cfg(not(feature = "env_style"))]
+        None => FormatOptions::default(),
+    }
+}
+
+/// A reusable set of formatting options.
+///
+/// A style is configured once and can then be applied to any number of errors with
+/// [`ReportStyle::report`]. The builder methods behave like the methods of [`Report`] with the
+/// same name. Options without a builder method can be set through [`FormatOptions`], which can
+/// be converted into a style.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::ReportStyle;
+///
+/// let style = ReportStyle::new().pretty(true).caused_by_label("Because:");
+///
+/// let error = std::io::Error::other("could not load config");
+/// let cause = std::io::Error::other("permission denied");
+/// let report = style.report(error).with_causes(vec![Box::new(cause)]);
+/// assert_eq!(
+///     report.to_string(),
+///     "could not load config\n\nBecause:\n      permission denied",
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReportStyle {
+    options: FormatOptions,
+}
+
+impl ReportStyle {
+    /// Create a style with the default options.
+    ///
+    /// This ignores the style set with [`set_default_style`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a report with this style.
+    pub fn report<E>(&self, error: E) -> Report<E>
+    where
+        E: Error,
+    {
+        Report::new(error).options(self.options.clone())
+    }
+
+    /// Returns the options of this style.
+    pub fn format_options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    /// See [`Report::pretty`].
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.options.pretty = pretty;
+        self
+    }
+
+    /// See [`Report::json`].
+    pub fn json(mut self, json: bool) -> Self {
+        self.options.json = json;
+        self
+    }
+
+    /// See [`Report::tree`].
+    pub fn tree(mut self, tree: bool) -> Self {
+        self.options.tree = tree;
+        self
+    }
+
+    /// See [`Report::plain`].
+    pub fn plain(mut self, plain: bool) -> Self {
+        self.options.plain = plain;
+        self
+    }
+
+    /// See [`Report::separator`].
+    pub fn separator(mut self, separator: impl Into<Cow<'static, str>>) -> Self {
+        self.options.separator = separator.into();
+        self
+    }
+
+    /// See [`Report::cause_prefix`].
+    pub fn cause_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.options.cause_prefix = prefix.into();
+        self
+    }
+
+    /// See [`Report::indentation`].
+    pub fn indentation(mut self, width: usize, fill: &str) -> Self {
+        self.options.indentation = Some(Cow::Owned(fill.repeat(width)));
+        self
+    }
+
+    /// See [`Report::caused_by_label`].
+    pub fn caused_by_label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.options.caused_by_label = label.into();
+        self
+    }
+
+    /// See [`Report::show_cause_types`].
+    pub fn show_cause_types(mut self, show: bool) -> Self {
+        self.options.show_cause_types = show;
+        self
+    }
+
+    /// See [`Report::wrap_at`].
+    pub fn wrap_at(mut self, width: usize) -> Self {
+        self.options.wrap_width = Some(width);
+        self
+    }
+
+    /// See [`Report::colored`].
+    pub fn colored(mut self, colored: bool) -> Self {
+        self.options.colored = colored;
+        self
+    }
+
+    /// See [`Report::theme`].
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.options.theme = theme;
+        self
+    }
+
+    /// See [`Report::max_causes`].
+    pub fn max_causes(mut self, max: usize) -> Self {
+        self.options.max_causes = Some(max);
+        self
+    }
+
+    /// See [`Report::max_cause_len`].
+    pub fn max_cause_len(mut self, max: usize) -> Self {
+        self.options.max_cause_len = Some(max);
+        self
+    }
+
+    /// See [`Report::max_len`].
+    pub fn max_len(mut self, max: usize) -> Self {
+        self.options.max_len = Some(max);
+        self
+    }
+}
+
+impl From<FormatOptions> for ReportStyle {
+    fn from(options: FormatOptions) -> Self {
+        ReportStyle { options }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::Report;
+
+impl<E, F> Report<E, F> {
+    /// Wrap the messages at the width of the terminal when pretty-printing.
+    ///
+    /// The width is detected once when this function is called. Stderr is checked first,
+    /// followed by stdout. If neither is a terminal, the configuration is not changed.
+    ///
+    /// See [`Report::wrap_at`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use error_reporter::Report;
+    ///
+    /// let error = std::io::Error::other("could not load config");
+    /// eprintln!("{}", Report::new(error).pretty(true).wrap_to_terminal());
+    /// ```
+    pub fn wrap_to_terminal(self) -> Self {
+        let size = terminal_size::terminal_size_of(std::io::stderr())
+            .or_else(terminal_size::terminal_size);
+        match size {
+            Some((terminal_size::Width(width), _)) => self.wrap_at(width as usize),
+            None => self,
+        }
+    }
+}
@@ -1,6 +1,10 @@
-use crate::Report;
+use crate::{
+    BoxStyle, CauseNumbering, CauseStrategy, CycleBehavior, DefaultStrategy, Dynamic,
+    DynamicReport, Json, Pretty, Report, ReportStyle, SingleLine, Theme,
+};
 use std::error::Error;
 use std::fmt::{Debug, Display, Formatter};
+use std::io::ErrorKind;
 
 #[derive(Debug)]
 struct E {
@@ -16,7 +20,7 @@ impl Display for E {
 
 impl Error for E {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
-        self.b.as_ref().map(|e| &**e)
+        self.b.as_deref()
     }
 }
 
@@ -70,3 +74,1814 @@ Caused by:
     assert_eq!(report.to_string(), SINGLE,);
     assert_eq!(report.pretty(true).to_string(), MULTI,);
 }
+
+#[test]
+fn tree() {
+    const UNICODE: &str = "\
+c
+├── b
+└── a";
+    const ASCII: &str = "\
+c
++-- b
++-- a";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true).tree(true);
+    assert_eq!(report.to_string(), UNICODE);
+    let report = report.box_style(BoxStyle::Ascii);
+    assert_eq!(report.to_string(), ASCII);
+}
+
+#[test]
+fn panel() {
+    const PANEL: &str = "\
++- a long title -+
+| c c c c: b: a  |
++----------------+";
+    const WRAPPED: &str = "\
+╭────────────╮
+│ c c        │
+│ c c        │
+│            │
+│ Caused by: │
+│    0: b    │
+│    1: a    │
+╰────────────╯";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c c c c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c)
+        .panel("a long title")
+        .box_style(BoxStyle::Ascii);
+    assert_eq!(report.to_string(), PANEL);
+    let report = report.panel("").box_style(BoxStyle::Unicode).pretty(true);
+    assert_eq!(format!("{report:8}"), WRAPPED);
+}
+
+#[test]
+fn colored_panel() {
+    const PANEL: &str = "\
+╭─ a title ──╮
+│ b          │
+│            │
+│ \x1b[1mCaused by:\x1b[0m │
+│       a    │
+╰────────────╯";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).panel("a\ntitle").pretty(true).colored(true);
+    assert_eq!(report.to_string(), PANEL);
+}
+
+#[test]
+fn nested() {
+    const NESTED: &str = "\
+c
+└── b
+    └── a
+        a";
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true).nested(true);
+    assert_eq!(report.to_string(), NESTED);
+}
+
+#[test]
+fn with_causes() {
+    const SINGLE: &str = "b: a: y: x";
+    const MULTI: &str = "\
+b
+
+Caused by:
+   0: a
+   1: y
+   2: x";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).with_causes(vec![
+        Box::new(std::io::Error::other("y")),
+        Box::new(std::io::Error::other("x")),
+    ]);
+    assert_eq!(report.to_string(), SINGLE);
+    assert_eq!(report.pretty(true).to_string(), MULTI);
+
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    let cause = std::io::Error::other("x");
+    assert_send_sync(&Report::new(std::io::Error::other("y")).with_causes(vec![Box::new(cause)]));
+}
+
+#[test]
+fn show_io_error_kind() {
+    const SINGLE: &str = "b: entity not found (NotFound)";
+    let b = E {
+        a: "b",
+        b: Some(Box::new(std::io::Error::from(ErrorKind::NotFound))),
+    };
+    let report = Report::from(b);
+    assert_eq!(report.to_string(), "b: entity not found");
+    assert_eq!(report.show_io_error_kind(true).to_string(), SINGLE);
+}
+
+#[test]
+fn html_list() {
+    const SINGLE: &str = "<b>: a & \"a\"";
+    const MULTI: &str = "<div>&lt;b&gt;</div><ol><li>a &amp; &quot;a&quot;</li></ol>";
+    let a = E {
+        a: "a & \"a\"",
+        b: None,
+    };
+    let b = E {
+        a: "<b>",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).html_list(true).html_root_tag("div");
+    assert_eq!(report.to_string(), SINGLE);
+    let report = report.pretty(true);
+    assert_eq!(report.to_string(), MULTI);
+    let report = report.html_root_tag("p onclick=alert(1)");
+    assert_eq!(report.to_string(), MULTI.replace("div", "p"));
+    let report = report.html_root_tag("p><script>");
+    assert_eq!(report.to_string(), MULTI.replace("div", "p"));
+}
+
+#[test]
+fn describe() {
+    const MULTI: &str = "\
+c
+
+Caused by:
+   0: b
+   1: a";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let description = Report::from(c).pretty(true).describe();
+    assert_eq!(description.root_message, "c");
+    assert_eq!(description.causes, ["b", "a"]);
+    assert!(description.pretty);
+    assert_eq!(description.to_string(), "c: b: a");
+    assert_eq!(description.to_string_pretty(), MULTI);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true);
+    let json = serde_json::to_string(&report.describe()).unwrap();
+    assert_eq!(json, r#"{"root_message":"b","causes":["a"],"pretty":true}"#);
+    let description: crate::ErrorDescription = serde_json::from_str(&json).unwrap();
+    assert_eq!(description.into_report().to_string(), report.to_string());
+}
+
+#[test]
+fn cause_numbering() {
+    const ALWAYS: &str = "\
+b
+
+Caused by:
+   0: a";
+    const NEVER: &str = "\
+c
+
+Caused by:
+      b
+      a";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b)
+        .pretty(true)
+        .cause_numbering(CauseNumbering::Always);
+    assert_eq!(report.to_string(), ALWAYS);
+    let c = E {
+        a: "c",
+        b: Some(Box::new(report.error)),
+    };
+    let report = Report::from(c)
+        .pretty(true)
+        .cause_numbering(CauseNumbering::Never);
+    assert_eq!(report.to_string(), NEVER);
+}
+
+#[test]
+fn map_sources() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).map_sources(Box::new(|index, error| {
+        (index == 1).then(|| format!("<{error}>"))
+    }));
+    assert_eq!(report.to_string(), "c: b: <a>");
+}
+
+#[test]
+fn format_frames() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c);
+    let frames = report.format_frames(|index, error, f| {
+        if index == 0 {
+            return Ok(());
+        }
+        write!(f, " <- {index}:{error}")
+    });
+    assert_eq!(frames.to_string(), "c <- 1:a");
+}
+
+#[test]
+fn register_hook() {
+    #[derive(Debug)]
+    struct Hooked;
+
+    impl Display for Hooked {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("hooked")
+        }
+    }
+
+    impl Error for Hooked {}
+
+    crate::register_hook::<Hooked>(|error, f| write!(f, "<{error}>"));
+    let b = E {
+        a: "b",
+        b: Some(Box::new(Hooked)),
+    };
+    let report = Report::from(b).pretty(true);
+    assert_eq!(report.to_string(), "b\n\nCaused by:\n      <hooked>");
+    let report = Report::from(Hooked);
+    assert_eq!(report.to_string(), "hooked");
+}
+
+#[cfg(feature = "gelf")]
+#[test]
+fn gelf() {
+    let a = E {
+        a: "\"a\"\n",
+        b: None,
+    };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true);
+    let gelf = report.to_gelf_string("host", 3);
+    assert_eq!(
+        gelf,
+        concat!(
+            r#"{"version":"1.1","host":"host","short_message":"b","#,
+            r#""full_message":"b\n\nCaused by:\n      \"a\"\n      ","#,
+            r#""level":3,"_cause_0":"\"a\"\n"}"#,
+        ),
+    );
+    let value: serde_json::Value = serde_json::from_str(&gelf).unwrap();
+    assert_eq!(value["_cause_0"], "\"a\"\n");
+}
+
+#[cfg(feature = "github_actions")]
+#[test]
+fn github_annotation() {
+    let a = E {
+        a: "100%\na",
+        b: None,
+    };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true);
+    assert_eq!(
+        report.to_github_annotation(Some("a:b,c"), None),
+        "::error file=a%3Ab%2Cc::b: 100%25; a",
+    );
+    assert_eq!(
+        report.to_github_annotation(None, Some(7)),
+        "::error line=7::b: 100%25; a",
+    );
+}
+
+#[test]
+fn cause_prefix() {
+    const MULTI: &str = "\
+b
+
+Caused by:
+      a";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).cause_prefix("because: ");
+    assert_eq!(report.to_string(), "b: because: a");
+    assert_eq!(report.pretty(true).to_string(), MULTI);
+}
+
+#[test]
+fn dynamic_report() {
+    const MULTI: &str = "\
+b
+
+Caused by:
+   0: a
+   1: x";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let mut report = DynamicReport::new(b).pretty(true);
+    report.extend([Box::new(std::io::Error::other("x")) as Box<dyn Error + Send + Sync>]);
+    assert_eq!(report.to_string(), MULTI);
+    assert_eq!(report.into_report().pretty(false).to_string(), "b: a: x");
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_layer() {
+    use crate::{FormatOptions, ReportLayer};
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    const OUTPUT: &str = "\
+ERROR request failed
+b
+
+Caused by:
+      a
+";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let buffer = Buffer::default();
+    let options = FormatOptions {
+        pretty: true,
+        ..Default::default()
+    };
+    let layer = ReportLayer::new(options).with_writer({
+        let buffer = buffer.clone();
+        move || buffer.clone()
+    });
+    let subscriber = tracing_subscriber::registry().with(layer);
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info!("ignored");
+        tracing::error!(err = &b as &dyn Error, "request failed");
+    });
+    let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(output, OUTPUT);
+}
+
+#[test]
+fn as_lines() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    assert_eq!(report.as_lines().collect::<Vec<_>>(), ["b: a"]);
+    let report = report.pretty(true);
+    assert_eq!(
+        report.as_lines().collect::<Vec<_>>(),
+        ["b", "", "Caused by:", "      a"],
+    );
+}
+
+#[test]
+fn min_number_digits() {
+    const MULTI: &str = "\
+c
+
+Caused by:
+  0: b
+  1: a
+     a";
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true).min_number_digits(3);
+    assert_eq!(report.to_string(), MULTI);
+}
+
+#[test]
+fn number_separator() {
+    const MULTI: &str = "\
+c
+
+Caused by:
+   0 - b
+   1 - a
+       a";
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true).number_separator(" - ");
+    assert_eq!(report.to_string(), MULTI);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn to_compact_string() {
+    let a = E {
+        a: "äää", b: None
+    };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true);
+    assert_eq!(report.to_compact_string::<9>().unwrap(), "b: äää");
+    assert_eq!(report.to_compact_string::<8>().unwrap_err(), "b: ä…");
+    assert_eq!(report.to_compact_string::<2>().unwrap_err(), "b:");
+}
+
+#[cfg(feature = "anyhow")]
+#[test]
+fn from_anyhow() {
+    const MULTI: &str = "\
+c
+
+Caused by:
+   0: b
+   1: a";
+    let error = anyhow::anyhow!("a").context("b").context("c");
+    let report = Report::from_anyhow(error);
+    assert_eq!(report.to_string(), "c: b: a");
+    assert_eq!(report.pretty(true).to_string(), MULTI);
+}
+
+#[test]
+fn show_cause_types() {
+    let e = std::any::type_name::<E>();
+    let io = std::any::type_name::<std::io::Error>();
+    let prefix = format!("   1 ({io}): ");
+    let multi = format!(
+        "c\n\nCaused by:\n   0 ({e}): b\n{prefix}a\n{}a",
+        " ".repeat(prefix.len()),
+    );
+    let a = std::io::Error::other("a\na");
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).show_cause_types(true);
+    assert_eq!(report.to_string(), format!("c: b: <{io}>a; a"));
+    let report = report.register_cause_type::<E>().pretty(true);
+    assert_eq!(report.to_string(), multi);
+}
+
+#[test]
+fn write_io() {
+    use crate::WriteIo;
+    use std::io::Write;
+
+    let mut writer = WriteIo::new(String::new());
+    let bytes = "aä€".as_bytes();
+    for b in bytes {
+        writer.write_all(std::slice::from_ref(b)).unwrap();
+    }
+    let err = writer.write_all(&[0xff]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(writer.into_inner(), "aä€");
+}
+
+#[test]
+fn depth_first_iter() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).with_causes(vec![Box::new(std::io::Error::other("x"))]);
+    let chain: Vec<_> = report
+        .depth_first_iter()
+        .map(|(depth, error)| (depth, error.to_string()))
+        .collect();
+    assert_eq!(chain, [(0, "b".into()), (1, "a".into()), (2, "x".into())]);
+}
+
+#[cfg(feature = "rfc7807")]
+#[test]
+fn problem_details() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    let json = report.to_problem_details(404, "https://example.org/not-found");
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "type": "https://example.org/not-found",
+            "title": "b",
+            "detail": "b: a",
+            "status": 404,
+        }),
+    );
+}
+
+#[derive(Debug)]
+struct Cyclic {
+    a: &'static str,
+    next: std::sync::OnceLock<&'static Cyclic>,
+}
+
+impl Display for Cyclic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.a)
+    }
+}
+
+impl Error for Cyclic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.next.get().map(|e| *e as _)
+    }
+}
+
+fn cyclic() -> &'static Cyclic {
+    let new = |a| {
+        &*Box::leak(Box::new(Cyclic {
+            a,
+            next: Default::default(),
+        }))
+    };
+    let a = new("a");
+    let b = new("b");
+    let c = new("c");
+    a.next.set(b).unwrap();
+    b.next.set(c).unwrap();
+    c.next.set(b).unwrap();
+    a
+}
+
+#[test]
+fn self_referential_cycle() {
+    let a = &*Box::leak(Box::new(Cyclic {
+        a: "a",
+        next: Default::default(),
+    }));
+    a.next.set(a).unwrap();
+    let report = Report::from(a).pretty(true);
+    assert_eq!(
+        report.to_string(),
+        "a\n\nCaused by:\n   0: a\n   1: <cycle detected>",
+    );
+}
+
+#[test]
+fn self_referential_root() {
+    #[derive(Debug)]
+    struct Loop(&'static str);
+
+    impl Display for Loop {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl Error for Loop {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(self)
+        }
+    }
+
+    let report = Report::from(Loop("a"));
+    assert_eq!(report.to_string(), "a: <cycle detected>");
+    assert_eq!(report.source_count(), 0);
+    let report = report.pretty(true);
+    assert_eq!(
+        report.to_string(),
+        "a\n\nCaused by:\n      <cycle detected>",
+    );
+}
+
+#[test]
+fn on_cycle() {
+    let report = Report::from(cyclic());
+    assert_eq!(report.to_string(), "a: b: c: <cycle detected>");
+    let report = report.on_cycle(CycleBehavior::Annotate("<cycle>".into()));
+    assert_eq!(report.to_string(), "a: b: c: <cycle>");
+    let report = report.on_cycle(CycleBehavior::Stop);
+    assert_eq!(report.to_string(), "a: b: c");
+}
+
+#[test]
+#[should_panic = "cycle detected in error chain: a: b: c"]
+fn on_cycle_panic() {
+    let report = Report::from(cyclic()).on_cycle(CycleBehavior::Panic);
+    let _ = report.to_string();
+}
+
+#[test]
+fn format_functions() {
+    struct Failure(E);
+
+    impl Display for Failure {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("failure: ")?;
+            crate::format_singleline(&self.0, &Default::default(), f)?;
+            f.write_str("\n")?;
+            crate::format_multiline(&self.0, &Default::default(), f)
+        }
+    }
+
+    const OUTPUT: &str = "\
+failure: b: a
+b
+
+Caused by:
+      a";
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    assert_eq!(Failure(b).to_string(), OUTPUT);
+}
+
+#[cfg(feature = "miette")]
+#[test]
+fn from_diagnostic() {
+    #[derive(Debug)]
+    struct D {
+        e: E,
+        related: Vec<D>,
+        help: Option<&'static str>,
+    }
+
+    impl Display for D {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            Display::fmt(&self.e, f)
+        }
+    }
+
+    impl Error for D {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.e.source()
+        }
+    }
+
+    impl miette::Diagnostic for D {
+        fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+            self.help.map(|h| Box::new(h) as _)
+        }
+
+        fn related<'a>(
+            &'a self,
+        ) -> Option<Box<dyn Iterator<Item = &'a dyn miette::Diagnostic> + 'a>> {
+            Some(Box::new(self.related.iter().map(|d| d as _)))
+        }
+    }
+
+    let related = D {
+        e: E {
+            a: "y",
+            b: Some(Box::new(E { a: "x", b: None })),
+        },
+        related: vec![],
+        help: Some("ignored"),
+    };
+    let d = D {
+        e: E {
+            a: "b",
+            b: Some(Box::new(E { a: "a", b: None })),
+        },
+        related: vec![related],
+        help: Some("h"),
+    };
+    let report = Report::from_diagnostic(d);
+    assert_eq!(report.to_string(), "b: a: y: x: help: h");
+}
+
+#[cfg(feature = "slog")]
+#[test]
+fn slog_value() {
+    struct Serializer(Vec<(String, String)>);
+
+    impl slog::Serializer for Serializer {
+        fn emit_arguments(&mut self, key: slog::Key, val: &std::fmt::Arguments) -> slog::Result {
+            self.0.push((key.to_string(), val.to_string()));
+            Ok(())
+        }
+    }
+
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    let mut serializer = Serializer(vec![]);
+    let args = format_args!("");
+    let record = slog::record!(slog::Level::Error, "", &args, slog::b!());
+    slog::Value::serialize(&report, &record, "error", &mut serializer).unwrap();
+    assert_eq!(serializer.0, [("error".to_string(), "b: a".to_string())]);
+}
+
+#[test]
+fn error_kind() {
+    let a = std::io::Error::from(ErrorKind::NotFound);
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    assert_eq!(crate::io_error_kind(&b), Some(ErrorKind::NotFound));
+    let report = Report::from(b);
+    assert_eq!(report.error_kind(), Some(ErrorKind::NotFound));
+    let report = Report::from(E { a: "a", b: None });
+    assert_eq!(report.error_kind(), None);
+}
+
+#[test]
+fn capitalize_messages() {
+    const MULTI: &str = "\
+C
+
+Caused by:
+   0: B
+   1: Äa";
+    let a = E { a: "äa", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).capitalize_messages(true);
+    assert_eq!(report.to_string(), "C: B: Äa");
+    assert_eq!(report.pretty(true).to_string(), MULTI);
+}
+
+#[test]
+fn try_from_str() {
+    use crate::{ChainedError, TryFromStrError};
+
+    let report = Report::<ChainedError>::try_from("c: b: a").unwrap();
+    assert_eq!(report.describe().causes, ["b", "a"]);
+    assert_eq!(report.to_string(), "c: b: a");
+    let err = Report::<ChainedError>::try_from("").unwrap_err();
+    assert_eq!(err, TryFromStrError::Empty);
+    let err = Report::<ChainedError>::try_from("c: : a").unwrap_err();
+    assert_eq!(err, TryFromStrError::Malformed);
+
+    let report = Report::<ChainedError>::try_from("c because b: a").unwrap();
+    assert_eq!(report.describe().causes, ["a"]);
+    let s = Report::from(ChainedError::new("c", ["b", "a"]))
+        .separator(" because ")
+        .to_string();
+    let report = Report::<ChainedError>::parse_with_separator(&s, " because ").unwrap();
+    assert_eq!(report.describe().causes, ["b", "a"]);
+    assert_eq!(report.to_string(), s);
+    let err = Report::<ChainedError>::parse_with_separator("c", "").unwrap_err();
+    assert_eq!(err, TryFromStrError::EmptySeparator);
+}
+
+#[test]
+fn bullet_list() {
+    const MULTI: &str = "\
+c
+
+Caused by:
+  • b
+  • a
+    a";
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true).use_bullet_list(true);
+    assert_eq!(report.to_string(), MULTI);
+    let report = report.box_style(BoxStyle::Ascii);
+    assert_eq!(report.to_string(), MULTI.replace('•', "-"));
+    let report = report.bullet('*');
+    assert_eq!(report.to_string(), MULTI.replace('•', "*"));
+}
+
+#[test]
+fn wrap_width() {
+    let a = E {
+        a: "the quick brown fox jumps over the lazy dog",
+        b: None,
+    };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).wrap_at(21);
+    assert_eq!(
+        report.to_string(),
+        "b\n\nCaused by:\n      the quick brown\n      fox jumps over\n      the lazy dog",
+    );
+    assert_eq!(
+        format!("{report:26}"),
+        "b\n\nCaused by:\n      the quick brown fox\n      jumps over the lazy\n      dog",
+    );
+    let report = report.pretty(false);
+    assert_eq!(
+        format!("{:8}|", Report::from(E { a: "a", b: None })),
+        "a       |"
+    );
+    assert_eq!(
+        format!("{report:>4}"),
+        "b: the quick brown fox jumps over the lazy dog",
+    );
+}
+
+#[test]
+fn with_strategy() {
+    struct Skip;
+
+    impl CauseStrategy for Skip {
+        fn causes<'a>(
+            &self,
+            error: &'a (dyn Error + 'static),
+        ) -> Box<dyn Iterator<Item = &'a (dyn Error + 'static)> + 'a> {
+            Box::new(DefaultStrategy.causes(error).skip(1))
+        }
+    }
+
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).with_strategy(DefaultStrategy);
+    assert_eq!(report.to_string(), "c: b: a");
+    let report = report.with_strategy(Skip);
+    assert_eq!(report.to_string(), "c: a");
+}
+
+#[test]
+fn into_format() {
+    let a = E { a: "a\"", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    let report = report.into_format::<Pretty>();
+    assert_eq!(report.to_string(), "b\n\nCaused by:\n      a\"");
+    let report = report.pretty(true).into_format::<SingleLine>();
+    assert_eq!(report.to_string(), "b: a\"");
+    let report = report.into_format::<Json>();
+    assert_eq!(report.to_string(), r#"{"message":"b","causes":["a\""]}"#);
+    let report = report.into_format::<Dynamic>();
+    assert_eq!(report.to_string(), "b\n\nCaused by:\n      a\"");
+}
+
+#[test]
+#[cfg(feature = "paginate")]
+fn pager() {
+    let mut pager = crate::paginate::Pager::new(10, 4);
+    assert!(pager.handle('j'));
+    assert_eq!(pager.top, 1);
+    pager.handle(' ');
+    assert_eq!(pager.top, 5);
+    pager.handle(' ');
+    assert_eq!(pager.top, 6);
+    pager.handle('k');
+    assert_eq!(pager.top, 5);
+    pager.handle('b');
+    pager.handle('b');
+    assert_eq!(pager.top, 0);
+    assert!(!pager.handle('q'));
+    use crate::paginate::truncate;
+    assert_eq!(truncate("abc", 2), "ab");
+    assert_eq!(truncate("\x1b[31mabc\x1b[0m", 1), "\x1b[31ma\x1b[0m",);
+    assert_eq!(
+        truncate("\x1b]8;;http://a\x1b\\abc\x1b]8;;\x1b\\", 2),
+        "\x1b]8;;http://a\x1b\\ab\x1b]8;;\x1b\\",
+    );
+}
+
+#[test]
+fn source_count() {
+    for len in [0, 1, 5, 100] {
+        let mut error = E { a: "a", b: None };
+        for _ in 0..len {
+            error = E {
+                a: "a",
+                b: Some(Box::new(error)),
+            };
+        }
+        let report = Report::from(error);
+        let walked = std::iter::successors(report.inner().source(), |&e| e.source()).count();
+        assert_eq!(report.source_count(), len);
+        assert_eq!(report.source_count(), walked);
+    }
+}
+
+#[test]
+#[cfg(feature = "serde_json")]
+fn to_json_value() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    assert_eq!(
+        report.to_json_value(),
+        serde_json::json!({ "message": "b", "causes": ["a"] }),
+    );
+}
+
+#[test]
+fn from_option() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from_option(Some(b));
+    assert_eq!(report.to_string(), "b: a");
+    let report = report.pretty(true);
+    assert_eq!(report.to_string(), "b\n\nCaused by:\n      a");
+    let report: Report<_> = None::<E>.into();
+    assert_eq!(report.to_string(), "no error");
+    let report = Report::from_option(None::<E>).pretty(true);
+    assert_eq!(report.to_string(), "no error");
+}
+
+#[test]
+fn precision() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c);
+    assert_eq!(format!("{report:.2}"), "c: b: a");
+    assert_eq!(format!("{report:.1}"), "c: b: ... and 1 more");
+    assert_eq!(format!("{report:>22.1}"), "  c: b: ... and 1 more");
+    assert_eq!(format!("{report:.0}"), "c: ... and 2 more");
+    let report = report.max_causes(1);
+    assert_eq!(format!("{report:.2}"), "c: b: ... and 1 more");
+    let report = report.pretty(true);
+    assert_eq!(
+        format!("{report:.0}"),
+        "c\n\nCaused by:\n      ... and 2 more"
+    );
+}
+
+#[test]
+fn alternate() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    assert_eq!(format!("{report:#}"), "b\n\nCaused by:\n      a");
+    assert_eq!(
+        format!("{report:#.0}"),
+        "b\n\nCaused by:\n      ... and 1 more"
+    );
+    let report = report.into_format::<SingleLine>();
+    assert_eq!(format!("{report:#}"), "b: a");
+}
+
+#[test]
+fn cause_section() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true);
+    let full = report.to_string();
+    let section = report.cause_section().to_string();
+    assert_eq!(section, "Caused by:\n   0: b\n   1: a");
+    assert_eq!(full, format!("c\n\n{section}"));
+    let report = Report::from(E { a: "a", b: None });
+    assert_eq!(report.cause_section().to_string(), "");
+}
+
+#[test]
+fn colored() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).colored(true);
+    assert_eq!(report.to_string(), "c: b: a");
+    let report = report.pretty(true);
+    assert_eq!(
+        report.to_string(),
+        "c\n\n\x1b[1mCaused by:\x1b[0m\n\x1b[33m   0: \x1b[0mb\n\x1b[33m   1: \x1b[0ma",
+    );
+    let theme = Theme {
+        header: "".into(),
+        index: "".into(),
+        message: "31".into(),
+        root_cause: "".into(),
+    };
+    let report = report.pretty(false).theme(theme);
+    assert_eq!(
+        report.to_string(),
+        "\x1b[31mc\x1b[0m: \x1b[31mb\x1b[0m: \x1b[31ma\x1b[0m",
+    );
+    let report = report.colored(false);
+    assert_eq!(report.to_string(), "c: b: a");
+    let report = report.colored(true).pretty(true).theme(Theme::dark());
+    assert_eq!(
+        report.to_string(),
+        "\x1b[38;5;252mc\x1b[0m\n\n\x1b[1;38;5;203mCaused by:\x1b[0m\n\
+         \x1b[38;5;221m   0: \x1b[0m\x1b[38;5;252mb\x1b[0m\n\
+         \x1b[38;5;221m   1: \x1b[0m\x1b[38;5;252ma\x1b[0m",
+    );
+    let report = report.theme(Theme::dark().plain_messages());
+    assert_eq!(
+        report.to_string(),
+        "c\n\n\x1b[1;38;5;203mCaused by:\x1b[0m\n\
+         \x1b[38;5;221m   0: \x1b[0mb\n\
+         \x1b[38;5;221m   1: \x1b[0ma",
+    );
+    let theme = Theme {
+        root_cause: "1".into(),
+        ..Theme::default()
+    };
+    let report = report.theme(theme).max_causes(1);
+    assert_eq!(
+        report.to_string(),
+        "c\n\n\x1b[1mCaused by:\x1b[0m\n\x1b[33m   0: \x1b[0mb\n      ... and 1 more",
+    );
+    let report = report.max_causes(2).tree(true);
+    assert_eq!(report.to_string(), "c\n├── b\n└── \x1b[1ma\x1b[0m");
+}
+
+#[test]
+#[cfg(feature = "color_auto")]
+fn color_auto() {
+    use crate::color::should_color;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        |name| {
+            vars.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    assert!(should_color(true, env(&[])));
+    assert!(!should_color(false, env(&[])));
+    assert!(!should_color(true, env(&[("TERM", "dumb")])));
+    assert!(!should_color(true, env(&[("NO_COLOR", "1")])));
+    assert!(should_color(true, env(&[("NO_COLOR", "")])));
+    assert!(!should_color(true, env(&[("CLICOLOR", "0")])));
+    assert!(should_color(false, env(&[("CLICOLOR_FORCE", "1")])));
+    assert!(should_color(
+        true,
+        env(&[("CLICOLOR_FORCE", "1"), ("NO_COLOR", "1")])
+    ));
+    assert!(!should_color(false, env(&[("CLICOLOR_FORCE", "0")])));
+}
+
+#[test]
+#[cfg(feature = "hyperlinks_auto")]
+fn hyperlinks_auto() {
+    use crate::hyperlinks::should_hyperlink;
+
+    fn env<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        |name| {
+            vars.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    assert!(!should_hyperlink(true, env(&[])));
+    assert!(!should_hyperlink(false, env(&[("WT_SESSION", "1")])));
+    assert!(should_hyperlink(true, env(&[("WT_SESSION", "1")])));
+    assert!(should_hyperlink(true, env(&[("VTE_VERSION", "6003")])));
+    assert!(!should_hyperlink(true, env(&[("VTE_VERSION", "4000")])));
+    assert!(should_hyperlink(true, env(&[("TERM_PROGRAM", "WezTerm")])));
+    assert!(should_hyperlink(false, env(&[("FORCE_HYPERLINK", "1")])));
+    assert!(!should_hyperlink(
+        true,
+        env(&[("FORCE_HYPERLINK", "0"), ("WT_SESSION", "1")])
+    ));
+}
+
+#[test]
+fn hyperlinks() {
+    let a = E {
+        a: "see http://a.example/x (or https://b.example)",
+        b: None,
+    };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).hyperlinks(true);
+    assert_eq!(
+        report.to_string(),
+        "b: see \x1b]8;;http://a.example/x\x1b\\http://a.example/x\x1b]8;;\x1b\\ \
+         (or \x1b]8;;https://b.example\x1b\\https://b.example\x1b]8;;\x1b\\)",
+    );
+}
+
+#[test]
+fn caused_by_label() {
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).caused_by_label("Because:");
+    assert_eq!(report.to_string(), "b\n\nBecause:\n      a\n      a");
+    let report = report.caused_by_label(String::from("Ursache:"));
+    assert_eq!(report.to_string(), "b\n\nUrsache:\n      a\n      a");
+}
+
+#[test]
+fn separator() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).separator(" | ");
+    assert_eq!(report.to_string(), "c | b | a");
+    let report = report.cause_prefix("<- ");
+    assert_eq!(report.to_string(), "c | <- b | <- a");
+    let report = report.pretty(true);
+    assert_eq!(report.to_string(), "c\n\nCaused by:\n   0: b\n   1: a");
+    let report = report.pretty(false).cause_prefix("").separator(" because ");
+    assert_eq!(report.to_string(), "c because b because a");
+}
+
+#[test]
+fn templates() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c)
+        .pretty(true)
+        .header_template("error: {message}")
+        .cause_template("  {index}) {message} {x}")
+        .number_from(1)
+        .max_causes(1);
+    assert_eq!(
+        report.to_string(),
+        "error: c\n\nCaused by:\n  1) b {x}\n  ) ... and 1 more {x}",
+    );
+}
+
+#[test]
+fn line_prefix() {
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).line_prefix("> ");
+    assert_eq!(report.to_string(), "> b: a; a");
+    let report = report.pretty(true);
+    assert_eq!(
+        report.to_string(),
+        "> b\n> \n> Caused by:\n>       a\n>       a"
+    );
+    let report = report.max_len(17);
+    assert_eq!(report.to_string(), "> … (truncated)");
+}
+
+#[test]
+fn blank_line_before_causes() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).blank_line_before_causes(false);
+    assert_eq!(report.to_string(), "b\nCaused by:\n      a");
+}
+
+#[test]
+fn crlf() {
+    let a = E {
+        a: "a\r\na",
+        b: None,
+    };
+    let b = E {
+        a: "b\nb",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).crlf(true).line_prefix("> ");
+    assert_eq!(
+        report.to_string(),
+        "> b\r\n> b\r\n> \r\n> Caused by:\r\n>       a\r\n>       a",
+    );
+}
+
+#[test]
+fn cargo_style() {
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).cargo_style(true);
+    assert_eq!(report.to_string(), "error: b\n\nCaused by:\n  a\n  a");
+    let report = Report::from(E { a: "b", b: None }).cargo_style(true);
+    assert_eq!(report.to_string(), "error: b");
+}
+
+#[test]
+fn rustc_style() {
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).rustc_style(true);
+    assert_eq!(report.to_string(), "error: b\n  = note: a\n          a");
+    let report = Report::from(E { a: "b", b: None })
+        .rustc_style(true)
+        .error_code("E0001");
+    assert_eq!(report.to_string(), "error[E0001]: b");
+}
+
+#[test]
+fn markdown() {
+    let a = E {
+        a: "```a```",
+        b: None,
+    };
+    let b = E {
+        a: "*b*",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).markdown(true);
+    assert_eq!(
+        report.to_string(),
+        "**\\*b\\***\n````\nCaused by:\n      ```a```\n````",
+    );
+    let report = Report::from(E { a: "b", b: None }).markdown(true);
+    assert_eq!(report.to_string(), "**b**");
+}
+
+#[test]
+fn title_prefix() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).title_prefix("Error");
+    assert_eq!(report.to_string(), "Error: b: a");
+    let report = report.pretty(true).line_prefix("> ");
+    assert_eq!(
+        format!("{report:?}"),
+        "> Error: b\n> \n> Caused by:\n>       a"
+    );
+    let report = report.json(true);
+    assert_eq!(report.to_string(), r#"> {"message":"b","causes":["a"]}"#);
+    let report = report.json(false).into_format::<Json>();
+    assert_eq!(report.to_string(), r#"> {"message":"b","causes":["a"]}"#);
+}
+
+#[test]
+fn footer() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).footer("c");
+    assert_eq!(report.to_string(), "b: a");
+    let report = report.pretty(true);
+    assert_eq!(report.to_string(), "b\n\nCaused by:\n      a\n\nc");
+    let report = report.tree(true);
+    assert_eq!(report.to_string(), "b\n└── a\n\nc");
+}
+
+#[test]
+fn program_name() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).program_name("prog").title_prefix("Error");
+    assert_eq!(report.to_string(), "prog: Error: b: a");
+    let report = report.cargo_style(true);
+    assert_eq!(report.to_string(), "prog: error: b\n\nCaused by:\n  a");
+    let report = report.json(true);
+    assert_eq!(report.to_string(), r#"{"message":"b","causes":["a"]}"#);
+    let name = Report::from(E { a: "b", b: None }).program_name_from_args();
+    assert!(name
+        .options
+        .program_name
+        .is_some_and(|name| !name.contains('/')));
+}
+
+#[test]
+fn plain() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).plain(true);
+    assert_eq!(report.to_string(), "b\nCaused by: a");
+    let report = report.caused_by_label("because");
+    assert_eq!(report.to_string(), "b\nbecause a");
+    let report = Report::from(E { a: "b", b: None }).pretty(true).plain(true);
+    assert_eq!(report.to_string(), "b");
+}
+
+#[test]
+fn parenthesized() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).parenthesized(true);
+    assert_eq!(report.to_string(), "c (caused by: b; a)");
+    let report = Report::from(E { a: "c", b: None }).parenthesized(true);
+    assert_eq!(report.to_string(), "c");
+}
+
+#[test]
+fn indentation() {
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true).indentation(1, "\t");
+    assert_eq!(report.to_string(), "c\n\nCaused by:\n   0: b\n   1: a\n\ta");
+    let report = report.cause_numbering(CauseNumbering::Never);
+    assert_eq!(report.to_string(), "c\n\nCaused by:\n\tb\n\ta\n\ta");
+}
+
+#[test]
+fn always_number() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).always_number(true);
+    assert_eq!(report.to_string(), "b\n\nCaused by:\n   0: a");
+    let report = report.always_number(false);
+    assert_eq!(report.to_string(), "b\n\nCaused by:\n      a");
+}
+
+#[test]
+fn number_from() {
+    let mut error = E { a: "a", b: None };
+    for _ in 0..9 {
+        error = E {
+            a: "a",
+            b: Some(Box::new(error)),
+        };
+    }
+    let report = Report::from(error)
+        .pretty(true)
+        .min_number_digits(1)
+        .number_from(1);
+    let output = report.to_string();
+    assert!(output.contains("\n1: a\n"));
+    assert!(output.ends_with("\n9: a"));
+    let report = report.number_from(2);
+    let output = report.to_string();
+    assert!(output.contains("\n 2: a\n"));
+    assert!(output.ends_with("\n10: a"));
+    let report = report.number_from(usize::MAX - 1);
+    let output = report.to_string();
+    assert!(output.contains(&format!("\n{}: a\n", usize::MAX - 1)));
+    assert!(output.ends_with(&format!("\n{}: a", usize::MAX)));
+    let report = report.cause_template("{index}. {message}");
+    assert!(report
+        .to_string()
+        .ends_with(&format!("\n{}. a", usize::MAX)));
+}
+
+#[test]
+fn reverse() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).reverse(true);
+    assert_eq!(report.to_string(), "a: b: c");
+    let report = report.pretty(true);
+    assert_eq!(report.to_string(), "a\n\nCaused by:\n   0: b\n   1: c");
+    let report = report.tree(true);
+    assert_eq!(report.to_string(), "a\n├── b\n└── c");
+    let report = Report::from(E { a: "a", b: None })
+        .reverse(true)
+        .pretty(true);
+    assert_eq!(report.to_string(), "a");
+}
+
+#[test]
+fn root_only() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).root_only(true);
+    assert_eq!(report.to_string(), "a");
+    let report = report.pretty(true).reverse(true);
+    assert_eq!(report.to_string(), "a");
+    let report = Report::from(E { a: "a", b: None }).root_only(true);
+    assert_eq!(report.to_string(), "a");
+}
+
+#[test]
+fn max_causes() {
+    let mut error = E { a: "a", b: None };
+    for _ in 0..4 {
+        error = E {
+            a: "a",
+            b: Some(Box::new(error)),
+        };
+    }
+    let report = Report::from(error).max_causes(2);
+    assert_eq!(report.to_string(), "a: a: a: ... and 2 more");
+    let report = report.pretty(true);
+    assert_eq!(
+        report.to_string(),
+        "a\n\nCaused by:\n   0: a\n   1: a\n      ... and 2 more",
+    );
+    let report = report.max_causes(4);
+    assert_eq!(report.source_count(), 4);
+    assert!(!report.to_string().contains("more"));
+    let report = report.max_causes(0).pretty(false);
+    assert_eq!(report.to_string(), "a: ... and 4 more");
+}
+
+#[test]
+fn collapse_duplicates() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "b",
+        b: Some(Box::new(b)),
+    };
+    let d = E {
+        a: "d",
+        b: Some(Box::new(c)),
+    };
+    let report = Report::from(d).collapse_duplicates(true);
+    assert_eq!(report.to_string(), "d: b (x2): a");
+    let report = report.pretty(true);
+    assert_eq!(report.to_string(), "d\n\nCaused by:\n   0: b (x2)\n   1: a");
+    let report = report.collapse_duplicates(false).pretty(false);
+    assert_eq!(report.to_string(), "d: b: b: a");
+}
+
+#[test]
+fn suppress_embedded() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b: a",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c: b: a",
+        b: Some(Box::new(b)),
+    };
+    let d = E {
+        a: "d",
+        b: Some(Box::new(c)),
+    };
+    let report = Report::from(d).suppress_embedded(true);
+    assert_eq!(report.to_string(), "d: c: b: a");
+    let report = report.pretty(true);
+    assert_eq!(report.to_string(), "d\n\nCaused by:\n      c: b: a");
+    let report = report.suppress_embedded(false).pretty(false);
+    assert_eq!(report.to_string(), "d: c: b: a: b: a: a");
+}
+
+#[test]
+fn newline_replacement() {
+    let a = E {
+        a: "a\r\na\na",
+        b: None,
+    };
+    let b = E {
+        a: "b\n",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    assert_eq!(report.to_string(), "b; : a; a; a");
+    let report = report.newline_replacement(Some("|".into()));
+    assert_eq!(report.to_string(), "b|: a|a|a");
+    let report = report.newline_replacement(None);
+    assert_eq!(report.to_string(), "b\n: a\r\na\na");
+    let report = report.pretty(true);
+    assert_eq!(
+        report.to_string(),
+        "b\n\n\nCaused by:\n      a\r\n      a\n      a"
+    );
+}
+
+#[test]
+fn sanitize() {
+    let a = E {
+        a: "a\x1b[31m\t\u{7f}\na",
+        b: None,
+    };
+    let b = E {
+        a: "b\x07",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).sanitize(true).capitalize_messages(true);
+    assert_eq!(report.to_string(), "B\\u{7}: A\\u{1b}[31m\\t\\u{7f}; a");
+    let report = report.pretty(true).colored(true);
+    assert_eq!(
+        report.to_string(),
+        "B\\u{7}\n\n\x1b[1mCaused by:\x1b[0m\n      A\\u{1b}[31m\\t\\u{7f}\n      a",
+    );
+}
+
+#[test]
+fn max_len() {
+    let a = E { a: "ä", b: None };
+    let b = E {
+        a: "bbbbbbbbbbbbbbb",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).max_len(19);
+    assert_eq!(report.to_string(), "bbbbbbbbbbbbbbb: ä");
+    let report = report.max_len(18);
+    assert_eq!(report.to_string(), "bbb… (truncated)");
+    let report = report.max_len(10);
+    assert_eq!(report.to_string(), "bbbbbbbbbb");
+    let report = report.max_len(24);
+    assert_eq!(format!("{report:.0}"), "bbbbbbbbb… (truncated)");
+    let report = report.max_len(16).pretty(true);
+    assert_eq!(report.to_string(), "b… (truncated)");
+}
+
+#[test]
+fn max_len_escapes() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).colored(true).max_len(28);
+    assert_eq!(report.to_string(), "b\n\n\x1b[1mCa\x1b[0m… (truncated)");
+    let report = report.max_len(5);
+    assert_eq!(report.to_string(), "b\n\n");
+    let report = report.colored(false).crlf(true).max_len(2);
+    assert_eq!(report.to_string(), "b");
+
+    let error = std::io::Error::other("see https://example.com");
+    let report = Report::new(error).hyperlinks(true).max_len(55);
+    assert_eq!(
+        report.to_string(),
+        "see \x1b]8;;https://example.com\x1b\\htt\x1b]8;;\x1b\\… (truncated)"
+    );
+}
+
+#[test]
+fn max_cause_len() {
+    let a = E {
+        a: "aaaa\naaaa",
+        b: None,
+    };
+    let b = E {
+        a: "bbbb",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "cccccc",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).max_cause_len(4);
+    assert_eq!(report.to_string(), "cccccc: bbbb: aaaa…");
+    let report = report.max_cause_len(6).pretty(true);
+    assert_eq!(
+        report.to_string(),
+        "cccccc\n\nCaused by:\n   0: bbbb\n   1: aaaa\n      a…",
+    );
+}
+
+#[test]
+#[cfg(feature = "unicode-width")]
+fn unicode_width() {
+    assert_eq!(crate::wrap::wrap("中文 中文", 5), "中文\n中文");
+    let a = E { a: "a\na", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let c = E {
+        a: "c",
+        b: Some(Box::new(b)),
+    };
+    let report = Report::from(c).pretty(true).number_separator("：");
+    assert_eq!(
+        report.to_string(),
+        "c\n\nCaused by:\n   0：b\n   1：a\n      a",
+    );
+    let report = report.use_bullet_list(true).bullet('❌');
+    assert_eq!(
+        report.to_string(),
+        "c\n\nCaused by:\n  ❌ b\n  ❌ a\n     a",
+    );
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "中文",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b);
+    assert_eq!(format!("{report:>9}"), "  中文: a");
+    assert_eq!(format!("{report:^9}"), " 中文: a ");
+}
+
+#[test]
+fn report_style() {
+    let style = ReportStyle::new().separator(" | ").max_causes(1);
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    assert_eq!(style.report(b).to_string(), "b | a");
+    let c = E { a: "c", b: None };
+    let d = E {
+        a: "d",
+        b: Some(Box::new(c)),
+    };
+    let e = E {
+        a: "e",
+        b: Some(Box::new(d)),
+    };
+    assert_eq!(style.report(e).to_string(), "e | d | ... and 1 more");
+    let style = ReportStyle::new().pretty(true).indentation(1, "\t");
+    let f = E { a: "f", b: None };
+    let g = E {
+        a: "g",
+        b: Some(Box::new(f)),
+    };
+    assert_eq!(style.report(g).to_string(), "g\n\nCaused by:\n\tf");
+}
+
+#[test]
+#[cfg(feature = "env_style")]
+fn env_style() {
+    use crate::env::parse_style;
+    assert!(!parse_style("compact").unwrap().format_options().pretty);
+    assert!(parse_style("pretty").unwrap().format_options().pretty);
+    assert!(parse_style("json").unwrap().format_options().json);
+    assert!(parse_style("verbose").is_none());
+}
+
+#[test]
+fn json() {
+    let a = E { a: "a", b: None };
+    let b = E {
+        a: "b",
+        b: Some(Box::new(a)),
+    };
+    let report = Report::from(b).pretty(true).json(true);
+    assert_eq!(report.to_string(), r#"{"message":"b","causes":["a"]}"#);
+}
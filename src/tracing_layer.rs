@@ -0,0 +1,101 @@
+use crate::{FormatOptions, Report};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use tracing_core::field::{Field, Visit};
+use tracing_core::{Event, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::{Context, Layer};
+
+/// A [`Layer`] that prints the errors attached to events as reports.
+///
+/// The layer looks for a field named `err` that was recorded as a `dyn Error`. If the event has
+/// such a field, the level and message of the event are written to the writer, followed by a
+/// report of the error formatted with the options passed to [`ReportLayer::new`]. Events
+/// without such a field are ignored.
+///
+/// By default, the output is written to stderr.
+///
+/// # Examples
+///
+/// ```rust
+/// use error_reporter::{FormatOptions, ReportLayer};
+/// use tracing_subscriber::layer::SubscriberExt;
+///
+/// let mut options = FormatOptions::default();
+/// options.pretty = true;
+/// let subscriber = tracing_subscriber::registry().with(ReportLayer::new(options));
+/// ```
+pub struct ReportLayer<W = fn() -> io::Stderr> {
+    options: FormatOptions,
+    make_writer: W,
+}
+
+impl ReportLayer {
+    /// Create a new layer that formats errors with the given options.
+    pub fn new(options: FormatOptions) -> Self {
+        ReportLayer {
+            options,
+            make_writer: io::stderr,
+        }
+    }
+}
+
+impl<W> ReportLayer<W> {
+    /// Set the writer that the reports are written to.
+    pub fn with_writer<W2>(self, make_writer: W2) -> ReportLayer<W2>
+    where
+        W2: for<'w> MakeWriter<'w> + 'static,
+    {
+        ReportLayer {
+            options: self.options,
+            make_writer,
+        }
+    }
+}
+
+impl<S, W> Layer<S> for ReportLayer<W>
+where
+    S: Subscriber,
+    W: for<'w> MakeWriter<'w> + 'static,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = ErrorVisitor {
+            options: &self.options,
+            message: None,
+            report: None,
+        };
+        event.record(&mut visitor);
+        let Some(report) = visitor.report else {
+            return;
+        };
+        let level = event.metadata().level();
+        let mut writer = self.make_writer.make_writer_for(event.metadata());
+        let _ = match visitor.message {
+            Some(message) => writeln!(writer, "{level} {message}\n{report}"),
+            None => writeln!(writer, "{level} {report}"),
+        };
+    }
+}
+
+/// Visitor that formats the `err` field of an event.
+struct ErrorVisitor<'a> {
+    options: &'a FormatOptions,
+    message: Option<String>,
+    report: Option<String>,
+}
+
+impl Visit for ErrorVisitor<'_> {
+    fn record_error(&mut self, field: &Field, value: &(dyn Error + 'static)) {
+        if field.name() == "err" {
+            let report = Report::new(value).options(self.options.clone());
+            self.report = Some(report.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        }
+    }
+}
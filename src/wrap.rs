@@ -0,0 +1,107 @@
+use std::fmt;
+
+/// Wrapper type for word-wrapping the output of the inner type.
+///
+/// If `width` is `None`, the output is not wrapped.
+pub(crate) struct Wrapped<D> {
+    pub(crate) inner: D,
+    pub(crate) width: Option<usize>,
+}
+
+impl<D> Wrapped<D> {
+    pub(crate) fn new(inner: D, width: Option<usize>) -> Self {
+        Wrapped { inner, width }
+    }
+}
+
+impl<D> fmt::Display for Wrapped<D>
+where
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.width {
+            None => write!(f, "{}", self.inner),
+            Some(width) => f.write_str(&wrap(&self.inner.to_string(), width)),
+        }
+    }
+}
+
+/// Returns the number of columns that `text` occupies in a terminal.
+///
+/// With the `unicode-width` feature, this is the display width of the text. Otherwise, each
+/// character is assumed to occupy one column.
+pub(crate) fn width_of(text: &str) -> usize {
+    #[cfg(feature = "unicode-width")]
+    {
+        unicode_width::UnicodeWidthStr::width(text)
+    }
+    #[cfg(not(feature = "unicode-width"))]
+    {
+        text.chars().count()
+    }
+}
+
+/// Returns the length in bytes of the escape sequence at the start of `text`.
+///
+/// CSI sequences end with a byte in the range `@` to `~` and OSC sequences end with BEL or
+/// ESC `\`. Other escape sequences consist of ESC and one character. An unterminated sequence
+/// extends to the end of `text`. Returns `None` if `text` does not start with ESC.
+pub(crate) fn escape_len(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix('\x1b')?;
+    let mut chars = rest.char_indices();
+    let end = match chars.next() {
+        Some((_, '[')) => chars.find(|&(_, c)| ('@'..='~').contains(&c)),
+        Some((_, ']')) => {
+            let mut escape = false;
+            chars.find(|&(_, c)| {
+                let end = c == '\x07' || (escape && c == '\\');
+                escape = c == '\x1b';
+                end
+            })
+        }
+        next => next,
+    };
+    let len = end.map_or(rest.len(), |(i, c)| i + c.len_utf8());
+    Some(1 + len)
+}
+
+/// Returns the number of columns that `text` occupies in a terminal, ignoring escape sequences.
+pub(crate) fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut rest = text;
+    while let Some(pos) = rest.find('\x1b') {
+        width += width_of(&rest[..pos]);
+        rest = &rest[pos..];
+        rest = &rest[escape_len(rest).unwrap_or_default()..];
+    }
+    width + width_of(rest)
+}
+
+/// Insert line breaks into `text` so that no line is wider than `width`.
+///
+/// Lines are only broken at spaces. Words that are wider than `width` are placed on their own
+/// line.
+pub(crate) fn wrap(text: &str, width: usize) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let mut column = 0;
+        for (j, word) in line.split(' ').enumerate() {
+            let len = width_of(word);
+            if j > 0 {
+                if column > 0 && column + 1 + len > width {
+                    out.push('\n');
+                    column = 0;
+                } else {
+                    out.push(' ');
+                    column += 1;
+                }
+            }
+            out.push_str(word);
+            column += len;
+        }
+    }
+    out
+}